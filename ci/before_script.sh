@@ -9,18 +9,25 @@ install_target_and_cross() {
             ;;
     esac
 
+    local sort=sort
+    local host=x86_64-unknown-linux-gnu
+    if test "$TRAVIS_OS_NAME" = "osx"; then
+        sort=gsort
+        host=x86_64-apple-darwin
+    fi
+
     # This fetches latest stable release
     local tag=$(git ls-remote --tags --refs --exit-code https://github.com/rust-embedded/cross \
                        | cut -d/ -f3 \
                        | grep -E '^v[0.1.0-9.]+$' \
-                       | gsort --version-sort \
+                       | $sort --version-sort \
                        | tail -n1)
     curl -LSfs https://japaric.github.io/trust/install.sh | \
         sh -s -- \
            --force \
            --git japaric/cross \
            --tag $tag \
-            --target x86_64-apple-darwin
+            --target $host
 }
 
 cargo_install() {
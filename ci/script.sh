@@ -29,5 +29,6 @@ else
     if [ -z $DISABLE_TESTS ]; then
         cross test --target $TARGET
         cross test --target $TARGET --release
+        cross test --target $TARGET --all-features
     fi
 fi
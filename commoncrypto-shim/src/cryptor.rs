@@ -0,0 +1,1068 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CCCryptor*` and `CCCrypt` functions.
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, BlockSizeUser, KeyInit};
+use aes::{Aes128, Aes192, Aes256};
+use blowfish::Blowfish;
+use cast5::Cast5;
+use commoncrypto_sys::{
+    kCCOptionECBMode, kCCOptionPKCS7Padding, CCAlgorithm, CCCryptorRef, CCCryptorStatus, CCMode,
+    CCModeOptions, CCOperation, CCOptions, CCPadding,
+};
+use des::{Des, TdesEde3};
+use ghash::universal_hash::UniversalHash;
+use ghash::GHash;
+use rc2::Rc2;
+use std::cmp;
+use std::mem;
+use std::os::raw::{c_int, c_uint};
+use {input, output, param, status, Status};
+
+/// Block cipher with a block size known at runtime.
+trait Cipher {
+    fn block_size(&self) -> usize;
+    fn encrypt(&self, block: &mut [u8]);
+    fn decrypt(&self, block: &mut [u8]);
+}
+
+impl<C: BlockEncrypt + BlockDecrypt> Cipher for C {
+    fn block_size(&self) -> usize {
+        <C as BlockSizeUser>::block_size()
+    }
+
+    fn encrypt(&self, block: &mut [u8]) {
+        self.encrypt_block(GenericArray::from_mut_slice(block));
+    }
+
+    fn decrypt(&self, block: &mut [u8]) {
+        self.decrypt_block(GenericArray::from_mut_slice(block));
+    }
+}
+
+fn new_cipher<C>(key: &[u8]) -> Status<Box<dyn Cipher>>
+where
+    C: KeyInit + BlockEncrypt + BlockDecrypt + 'static,
+{
+    match C::new_from_slice(key) {
+        Ok(cipher) => Ok(Box::new(cipher)),
+        Err(_) => Err(CCCryptorStatus::kCCKeySizeError),
+    }
+}
+
+fn block_cipher(algorithm: CCAlgorithm, key: &[u8]) -> Status<Box<dyn Cipher>> {
+    match algorithm {
+        CCAlgorithm::kCCAlgorithmAES => match key.len() {
+            16 => new_cipher::<Aes128>(key),
+            24 => new_cipher::<Aes192>(key),
+            32 => new_cipher::<Aes256>(key),
+            _ => Err(CCCryptorStatus::kCCKeySizeError),
+        },
+        CCAlgorithm::kCCAlgorithmDES => new_cipher::<Des>(key),
+        CCAlgorithm::kCCAlgorithm3DES => new_cipher::<TdesEde3>(key),
+        CCAlgorithm::kCCAlgorithmCAST => new_cipher::<Cast5>(key),
+        CCAlgorithm::kCCAlgorithmRC2 => new_cipher::<Rc2>(key),
+        // CommonCrypto requires at least 64-bit Blowfish keys
+        CCAlgorithm::kCCAlgorithmBlowfish if key.len() >= 8 => new_cipher::<Blowfish>(key),
+        CCAlgorithm::kCCAlgorithmBlowfish => Err(CCCryptorStatus::kCCKeySizeError),
+        CCAlgorithm::kCCAlgorithmRC4 => Err(CCCryptorStatus::kCCParamError),
+    }
+}
+
+/// RC4 stream cipher.
+#[derive(Clone)]
+struct Rc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl Rc4 {
+    fn new(key: &[u8]) -> Status<Rc4> {
+        if key.is_empty() || key.len() > 512 {
+            return Err(CCCryptorStatus::kCCKeySizeError);
+        }
+        let mut state = [0u8; 256];
+        for (i, byte) in state.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut j = 0u8;
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, j as usize);
+        }
+        Ok(Rc4 { state, i: 0, j: 0 })
+    }
+
+    fn apply(&mut self, data: &mut [u8]) {
+        for byte in data {
+            self.i = self.i.wrapping_add(1);
+            self.j = self.j.wrapping_add(self.state[self.i as usize]);
+            self.state.swap(self.i as usize, self.j as usize);
+            let k = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+            *byte ^= self.state[k as usize];
+        }
+    }
+}
+
+/// Block cipher modes which turn the cipher into a stream cipher.
+#[derive(Clone, Copy, PartialEq)]
+enum StreamMode {
+    Cfb,
+    Cfb8,
+    Ctr,
+    Ofb,
+}
+
+fn increment_be(counter: &mut [u8]) {
+    for byte in counter.iter_mut().rev() {
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum GcmState {
+    Iv,
+    Aad,
+    Data,
+    Finalized,
+}
+
+/// Streaming AES-GCM, as specified in NIST SP 800-38D.
+struct Gcm {
+    cipher: Box<dyn Cipher>,
+    state: GcmState,
+    iv: Vec<u8>,
+    ghash: Option<GHash>,
+    pending: Vec<u8>,
+    counter: [u8; 16],
+    keystream: [u8; 16],
+    used: usize,
+    tag_mask: [u8; 16],
+    aad_len: u64,
+    data_len: u64,
+}
+
+impl Gcm {
+    fn new(cipher: Box<dyn Cipher>) -> Gcm {
+        Gcm {
+            cipher,
+            state: GcmState::Iv,
+            iv: Vec::new(),
+            ghash: None,
+            pending: Vec::new(),
+            counter: [0; 16],
+            keystream: [0; 16],
+            used: 16,
+            tag_mask: [0; 16],
+            aad_len: 0,
+            data_len: 0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.state = GcmState::Iv;
+        self.iv.clear();
+        self.ghash = None;
+        self.pending.clear();
+        self.used = 16;
+        self.aad_len = 0;
+        self.data_len = 0;
+    }
+
+    fn add_iv(&mut self, iv: &[u8]) -> Status<()> {
+        if self.state != GcmState::Iv {
+            return Err(CCCryptorStatus::kCCCallSequenceError);
+        }
+        if iv.is_empty() {
+            return Err(CCCryptorStatus::kCCParamError);
+        }
+        self.iv.extend_from_slice(iv);
+        Ok(())
+    }
+
+    fn new_ghash(&self) -> GHash {
+        let mut hash_key = [0u8; 16];
+        self.cipher.encrypt(&mut hash_key);
+        GHash::new(GenericArray::from_slice(&hash_key))
+    }
+
+    /// Derives the initial counter block from the IV once it is complete.
+    fn start(&mut self) -> Status<()> {
+        if self.state != GcmState::Iv {
+            return Ok(());
+        }
+        if self.iv.is_empty() {
+            return Err(CCCryptorStatus::kCCCallSequenceError);
+        }
+        let mut j0 = [0u8; 16];
+        if self.iv.len() == 12 {
+            j0[..12].copy_from_slice(&self.iv);
+            j0[15] = 1;
+        } else {
+            let mut ghash = self.new_ghash();
+            ghash.update_padded(&self.iv);
+            let mut lengths = [0u8; 16];
+            lengths[8..].copy_from_slice(&(self.iv.len() as u64 * 8).to_be_bytes());
+            ghash.update_padded(&lengths);
+            j0.copy_from_slice(&ghash.finalize());
+        }
+        self.tag_mask = j0;
+        self.cipher.encrypt(&mut self.tag_mask);
+        self.counter = j0;
+        increment_be(&mut self.counter[12..]);
+        self.ghash = Some(self.new_ghash());
+        self.state = GcmState::Aad;
+        Ok(())
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        self.pending.extend_from_slice(data);
+        let full = self.pending.len() / 16 * 16;
+        if let Some(ref mut ghash) = self.ghash {
+            ghash.update_padded(&self.pending[..full]);
+        }
+        self.pending.drain(..full);
+    }
+
+    fn absorb_padding(&mut self) {
+        if let Some(ref mut ghash) = self.ghash {
+            ghash.update_padded(&self.pending);
+        }
+        self.pending.clear();
+    }
+
+    fn add_aad(&mut self, aad: &[u8]) -> Status<()> {
+        self.start()?;
+        if self.state != GcmState::Aad {
+            return Err(CCCryptorStatus::kCCCallSequenceError);
+        }
+        self.aad_len += aad.len() as u64;
+        self.absorb(aad);
+        Ok(())
+    }
+
+    fn crypt(&mut self, op: CCOperation, data: &mut [u8]) -> Status<()> {
+        self.start()?;
+        match self.state {
+            GcmState::Aad => {
+                self.absorb_padding();
+                self.state = GcmState::Data;
+            }
+            GcmState::Finalized => return Err(CCCryptorStatus::kCCCallSequenceError),
+            _ => (),
+        }
+        if op == CCOperation::kCCDecrypt {
+            self.absorb(data);
+        }
+        for byte in data.iter_mut() {
+            if self.used == 16 {
+                self.keystream = self.counter;
+                self.cipher.encrypt(&mut self.keystream);
+                increment_be(&mut self.counter[12..]);
+                self.used = 0;
+            }
+            *byte ^= self.keystream[self.used];
+            self.used += 1;
+        }
+        if op == CCOperation::kCCEncrypt {
+            self.absorb(data);
+        }
+        self.data_len += data.len() as u64;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Status<[u8; 16]> {
+        self.start()?;
+        if self.state == GcmState::Finalized {
+            return Err(CCCryptorStatus::kCCCallSequenceError);
+        }
+        self.absorb_padding();
+        let mut lengths = [0u8; 16];
+        lengths[..8].copy_from_slice(&(self.aad_len * 8).to_be_bytes());
+        lengths[8..].copy_from_slice(&(self.data_len * 8).to_be_bytes());
+        let mut ghash = self.ghash.take().expect("GHASH is started");
+        ghash.update_padded(&lengths);
+        let mut tag = self.tag_mask;
+        for (tag, s) in tag.iter_mut().zip(ghash.finalize().iter()) {
+            *tag ^= s;
+        }
+        self.state = GcmState::Finalized;
+        Ok(tag)
+    }
+}
+
+enum Engine {
+    /// ECB (without a chaining block) or CBC, which process whole blocks and may pad the input.
+    Block {
+        cipher: Box<dyn Cipher>,
+        padding: bool,
+        chain: Option<Vec<u8>>,
+        buffer: Vec<u8>,
+    },
+    Stream {
+        cipher: Box<dyn Cipher>,
+        mode: StreamMode,
+        register: Vec<u8>,
+        keystream: Vec<u8>,
+        used: usize,
+    },
+    Rc4 {
+        initial: Box<Rc4>,
+        rc4: Box<Rc4>,
+    },
+    Gcm(Gcm),
+}
+
+/// Cryptographic context behind a `CCCryptorRef`.
+struct Cryptor {
+    op: CCOperation,
+    engine: Engine,
+}
+
+/// Reads a block-sized IV, which is all zeros if `iv` is null.
+unsafe fn read_iv(iv: *const u8, block_size: usize) -> Vec<u8> {
+    if iv.is_null() {
+        vec![0; block_size]
+    } else {
+        input(iv, block_size).expect("IV is not null").to_vec()
+    }
+}
+
+impl Cryptor {
+    unsafe fn new(
+        op: CCOperation,
+        mode: CCMode,
+        algorithm: CCAlgorithm,
+        padding: CCPadding,
+        iv: *const u8,
+        key: &[u8],
+    ) -> Status<Cryptor> {
+        let stream_mode = match (algorithm, mode) {
+            (CCAlgorithm::kCCAlgorithmRC4, CCMode::kCCModeRC4) => {
+                let rc4 = Box::new(Rc4::new(key)?);
+                let engine = Engine::Rc4 {
+                    initial: rc4.clone(),
+                    rc4,
+                };
+                return Ok(Cryptor { op, engine });
+            }
+            (CCAlgorithm::kCCAlgorithmRC4, _) | (_, CCMode::kCCModeRC4) => {
+                return Err(CCCryptorStatus::kCCParamError)
+            }
+            (CCAlgorithm::kCCAlgorithmAES, CCMode::kCCModeGCM) => {
+                let engine = Engine::Gcm(Gcm::new(block_cipher(algorithm, key)?));
+                return Ok(Cryptor { op, engine });
+            }
+            (_, CCMode::kCCModeGCM) => return Err(CCCryptorStatus::kCCParamError),
+            (_, CCMode::kCCModeXTS) | (_, CCMode::kCCModeCCM) => {
+                return Err(CCCryptorStatus::kCCUnimplemented)
+            }
+            (_, CCMode::kCCModeECB) | (_, CCMode::kCCModeCBC) => None,
+            (_, CCMode::kCCModeCFB) => Some(StreamMode::Cfb),
+            (_, CCMode::kCCModeCFB8) => Some(StreamMode::Cfb8),
+            (_, CCMode::kCCModeCTR) => Some(StreamMode::Ctr),
+            (_, CCMode::kCCModeOFB) => Some(StreamMode::Ofb),
+        };
+        let cipher = block_cipher(algorithm, key)?;
+        let iv = read_iv(iv, cipher.block_size());
+        let engine = match stream_mode {
+            Some(mode) => Engine::Stream {
+                used: cipher.block_size(),
+                keystream: vec![0; cipher.block_size()],
+                cipher,
+                mode,
+                register: iv,
+            },
+            None => Engine::Block {
+                cipher,
+                padding: padding == CCPadding::ccPKCS7Padding,
+                chain: if mode == CCMode::kCCModeCBC {
+                    Some(iv)
+                } else {
+                    None
+                },
+                buffer: Vec::new(),
+            },
+        };
+        Ok(Cryptor { op, engine })
+    }
+
+    fn output_len(&self, input_len: usize, final_: bool) -> usize {
+        match self.engine {
+            Engine::Block {
+                ref cipher,
+                padding,
+                ref buffer,
+                ..
+            } => {
+                let block_size = cipher.block_size();
+                let total = buffer.len() + input_len;
+                let encrypt = self.op == CCOperation::kCCEncrypt;
+                if final_ {
+                    if encrypt && padding {
+                        (total / block_size + 1) * block_size
+                    } else {
+                        total
+                    }
+                } else {
+                    let whole = total / block_size * block_size;
+                    // When decrypting with padding, the last block is held back until the end
+                    if !encrypt && padding && whole == total && whole > 0 {
+                        whole - block_size
+                    } else {
+                        whole
+                    }
+                }
+            }
+            _ => input_len,
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) -> Status<Vec<u8>> {
+        let len = self.output_len(data.len(), false);
+        let op = self.op;
+        match self.engine {
+            Engine::Block {
+                ref cipher,
+                ref mut chain,
+                ref mut buffer,
+                ..
+            } => {
+                buffer.extend_from_slice(data);
+                let mut out: Vec<u8> = buffer.drain(..len).collect();
+                crypt_blocks(op, &**cipher, chain, &mut out);
+                Ok(out)
+            }
+            Engine::Stream {
+                ref cipher,
+                mode,
+                ref mut register,
+                ref mut keystream,
+                ref mut used,
+            } => {
+                let mut out = data.to_vec();
+                for byte in out.iter_mut() {
+                    let block_size = register.len();
+                    if mode == StreamMode::Cfb8 {
+                        keystream.copy_from_slice(register);
+                        cipher.encrypt(keystream);
+                        let input = *byte;
+                        *byte ^= keystream[0];
+                        register.rotate_left(1);
+                        register[block_size - 1] = if op == CCOperation::kCCEncrypt {
+                            *byte
+                        } else {
+                            input
+                        };
+                        continue;
+                    }
+                    if *used == block_size {
+                        if mode == StreamMode::Ctr {
+                            keystream.copy_from_slice(register);
+                            cipher.encrypt(keystream);
+                            increment_be(register);
+                        } else {
+                            // OFB feeds back the keystream, CFB the ciphertext
+                            cipher.encrypt(register);
+                            keystream.copy_from_slice(register);
+                        }
+                        *used = 0;
+                    }
+                    let input = *byte;
+                    *byte ^= keystream[*used];
+                    if mode == StreamMode::Cfb {
+                        register[*used] = if op == CCOperation::kCCEncrypt {
+                            *byte
+                        } else {
+                            input
+                        };
+                    }
+                    *used += 1;
+                }
+                Ok(out)
+            }
+            Engine::Rc4 { ref mut rc4, .. } => {
+                let mut out = data.to_vec();
+                rc4.apply(&mut out);
+                Ok(out)
+            }
+            Engine::Gcm(ref mut gcm) => {
+                let mut out = data.to_vec();
+                gcm.crypt(op, &mut out)?;
+                Ok(out)
+            }
+        }
+    }
+
+    // usize::is_multiple_of is newer than the oldest supported Rust.
+    #[allow(clippy::manual_is_multiple_of)]
+    fn finish(&mut self) -> Status<Vec<u8>> {
+        let op = self.op;
+        if let Engine::Block {
+            ref cipher,
+            padding,
+            ref mut chain,
+            ref mut buffer,
+        } = self.engine
+        {
+            let block_size = cipher.block_size();
+            let mut out = mem::take(buffer);
+            if op == CCOperation::kCCEncrypt && padding {
+                let pad = block_size - out.len() % block_size;
+                out.resize(out.len() + pad, pad as u8);
+            } else if out.len() % block_size != 0 || (padding && out.is_empty()) {
+                return Err(CCCryptorStatus::kCCAlignmentError);
+            }
+            crypt_blocks(op, &**cipher, chain, &mut out);
+            if op == CCOperation::kCCDecrypt && padding {
+                let pad = out[out.len() - 1] as usize;
+                if pad == 0
+                    || pad > block_size
+                    || out[out.len() - pad..].iter().any(|&b| b as usize != pad)
+                {
+                    return Err(CCCryptorStatus::kCCDecodeError);
+                }
+                out.truncate(out.len() - pad);
+            }
+            Ok(out)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    unsafe fn reset(&mut self, iv: *const u8) {
+        match self.engine {
+            Engine::Block {
+                ref cipher,
+                ref mut chain,
+                ref mut buffer,
+                ..
+            } => {
+                if chain.is_some() {
+                    *chain = Some(read_iv(iv, cipher.block_size()));
+                }
+                buffer.clear();
+            }
+            Engine::Stream {
+                ref cipher,
+                ref mut register,
+                ref mut used,
+                ..
+            } => {
+                *register = read_iv(iv, cipher.block_size());
+                *used = register.len();
+            }
+            Engine::Rc4 {
+                ref initial,
+                ref mut rc4,
+            } => *rc4 = initial.clone(),
+            Engine::Gcm(ref mut gcm) => gcm.reset(),
+        }
+    }
+}
+
+/// Encrypts or decrypts whole blocks in ECB mode, or CBC mode if there is a chaining block.
+fn crypt_blocks(
+    op: CCOperation,
+    cipher: &dyn Cipher,
+    chain: &mut Option<Vec<u8>>,
+    data: &mut [u8],
+) {
+    for block in data.chunks_mut(cipher.block_size()) {
+        match (op, chain.as_mut()) {
+            (CCOperation::kCCEncrypt, None) => cipher.encrypt(block),
+            (CCOperation::kCCDecrypt, None) => cipher.decrypt(block),
+            (CCOperation::kCCEncrypt, Some(chain)) => {
+                for (byte, c) in block.iter_mut().zip(chain.iter()) {
+                    *byte ^= c;
+                }
+                cipher.encrypt(block);
+                chain.copy_from_slice(block);
+            }
+            (CCOperation::kCCDecrypt, Some(chain)) => {
+                let ciphertext = block.to_vec();
+                cipher.decrypt(block);
+                for (byte, c) in block.iter_mut().zip(chain.iter()) {
+                    *byte ^= c;
+                }
+                chain.copy_from_slice(&ciphertext);
+            }
+        }
+    }
+}
+
+unsafe fn set_moved(data_out_moved: *mut usize, len: usize) {
+    if let Some(moved) = data_out_moved.as_mut() {
+        *moved = len;
+    }
+}
+
+fn mode_from_options(algorithm: CCAlgorithm, options: CCOptions) -> (CCMode, CCPadding) {
+    let mode = if algorithm == CCAlgorithm::kCCAlgorithmRC4 {
+        CCMode::kCCModeRC4
+    } else if options & kCCOptionECBMode != 0 {
+        CCMode::kCCModeECB
+    } else {
+        CCMode::kCCModeCBC
+    };
+    let padding = if options & kCCOptionPKCS7Padding != 0 {
+        CCPadding::ccPKCS7Padding
+    } else {
+        CCPadding::ccNoPadding
+    };
+    (mode, padding)
+}
+
+unsafe fn cryptor<'a>(cryptor_ref: CCCryptorRef) -> Status<&'a mut Cryptor> {
+    (cryptor_ref as *mut Cryptor)
+        .as_mut()
+        .ok_or(CCCryptorStatus::kCCParamError)
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe fn create(
+    op: CCOperation,
+    mode: CCMode,
+    alg: CCAlgorithm,
+    padding: CCPadding,
+    iv: *const u8,
+    key: *const u8,
+    key_length: usize,
+    cryptor_ref: *mut CCCryptorRef,
+) -> Status<()> {
+    let cryptor_ref = cryptor_ref.as_mut().ok_or(CCCryptorStatus::kCCParamError)?;
+    let cryptor = Cryptor::new(op, mode, alg, padding, iv, input(key, key_length)?)?;
+    *cryptor_ref = Box::into_raw(Box::new(cryptor)) as CCCryptorRef;
+    Ok(())
+}
+
+/// Create a cryptographic context. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorCreate(
+    op: c_uint,
+    alg: c_uint,
+    options: CCOptions,
+    key: *const u8,
+    keyLength: usize,
+    iv: *const u8,
+    cryptorRef: *mut CCCryptorRef,
+) -> c_int {
+    fail_point!("CCCryptorCreate");
+    status(param(alg).and_then(|alg| {
+        let (mode, padding) = mode_from_options(alg, options);
+        create(
+            param(op)?,
+            mode,
+            alg,
+            padding,
+            iv,
+            key,
+            keyLength,
+            cryptorRef,
+        )
+    }))
+}
+
+/// Create a cryptographic context using the given block cipher mode. Returns `0` on success.
+///
+/// The tweak, number of rounds and mode options are not used, since XTS mode is not supported
+/// and counters are always big-endian.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorCreateWithMode(
+    op: c_uint,
+    mode: c_uint,
+    alg: c_uint,
+    padding: c_uint,
+    iv: *const u8,
+    key: *const u8,
+    keyLength: usize,
+    _tweak: *const u8,
+    _tweakLength: usize,
+    _numRounds: c_int,
+    _options: CCModeOptions,
+    cryptorRef: *mut CCCryptorRef,
+) -> c_int {
+    fail_point!("CCCryptorCreateWithMode");
+    status(param(op).and_then(|op| {
+        create(
+            op,
+            param(mode)?,
+            param(alg)?,
+            param(padding)?,
+            iv,
+            key,
+            keyLength,
+            cryptorRef,
+        )
+    }))
+}
+
+/// Free a context created by `CCCryptorCreate*()`. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorRelease(cryptorRef: CCCryptorRef) -> c_int {
+    fail_point!("CCCryptorRelease");
+    if !cryptorRef.is_null() {
+        drop(Box::from_raw(cryptorRef as *mut Cryptor));
+    }
+    status(Ok(()))
+}
+
+/// Process (encrypt or decrypt) some data. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorUpdate(
+    cryptorRef: CCCryptorRef,
+    dataIn: *const u8,
+    dataInLength: usize,
+    dataOut: *mut u8,
+    dataOutAvailable: usize,
+    dataOutMoved: *mut usize,
+) -> c_int {
+    fail_point!("CCCryptorUpdate");
+    status(cryptor(cryptorRef).and_then(|cryptor| {
+        let needed = cryptor.output_len(dataInLength, false);
+        if dataOutAvailable < needed {
+            set_moved(dataOutMoved, needed);
+            return Err(CCCryptorStatus::kCCBufferTooSmall);
+        }
+        let processed = cryptor.update(input(dataIn, dataInLength)?)?;
+        output(dataOut, processed.len())?.copy_from_slice(&processed);
+        set_moved(dataOutMoved, processed.len());
+        Ok(())
+    }))
+}
+
+/// Finish an encrypt or decrypt operation. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorFinal(
+    cryptorRef: CCCryptorRef,
+    dataOut: *mut u8,
+    dataOutAvailable: usize,
+    dataOutMoved: *mut usize,
+) -> c_int {
+    fail_point!("CCCryptorFinal");
+    status(cryptor(cryptorRef).and_then(|cryptor| {
+        let needed = cryptor.output_len(0, true);
+        if dataOutAvailable < needed {
+            set_moved(dataOutMoved, needed);
+            return Err(CCCryptorStatus::kCCBufferTooSmall);
+        }
+        let processed = cryptor.finish()?;
+        output(dataOut, processed.len())?.copy_from_slice(&processed);
+        set_moved(dataOutMoved, processed.len());
+        Ok(())
+    }))
+}
+
+/// Determine the output buffer size required to process the given input size.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGetOutputLength(
+    cryptorRef: CCCryptorRef,
+    inputLength: usize,
+    final_: bool,
+) -> usize {
+    cryptor(cryptorRef).map_or(0, |cryptor| cryptor.output_len(inputLength, final_))
+}
+
+/// Reinitialize an existing `CCCryptorRef` with a (possibly) new initialization vector.
+/// Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorReset(cryptorRef: CCCryptorRef, iv: *const u8) -> c_int {
+    fail_point!("CCCryptorReset");
+    status(cryptor(cryptorRef).map(|cryptor| {
+        cryptor.reset(iv);
+    }))
+}
+
+unsafe fn gcm<'a>(cryptor_ref: CCCryptorRef) -> Status<&'a mut Gcm> {
+    match cryptor(cryptor_ref)? {
+        Cryptor {
+            engine: Engine::Gcm(ref mut gcm),
+            ..
+        } => Ok(gcm),
+        _ => Err(CCCryptorStatus::kCCParamError),
+    }
+}
+
+unsafe fn gcm_crypt(
+    op: CCOperation,
+    cryptor_ref: CCCryptorRef,
+    data_in: *const u8,
+    data_in_length: usize,
+    data_out: *mut u8,
+) -> c_int {
+    status(gcm(cryptor_ref).and_then(|gcm| {
+        let mut data = input(data_in, data_in_length)?.to_vec();
+        gcm.crypt(op, &mut data)?;
+        output(data_out, data.len())?.copy_from_slice(&data);
+        Ok(())
+    }))
+}
+
+/// Add the initialization vector to a GCM `CCCryptorRef`. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMAddIV(
+    cryptorRef: CCCryptorRef,
+    iv: *const u8,
+    ivLen: usize,
+) -> c_int {
+    fail_point!("CCCryptorGCMAddIV");
+    status(gcm(cryptorRef).and_then(|gcm| gcm.add_iv(input(iv, ivLen)?)))
+}
+
+/// Add additional authenticated data to a GCM `CCCryptorRef`. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMAddAAD(
+    cryptorRef: CCCryptorRef,
+    aData: *const u8,
+    aDataLen: usize,
+) -> c_int {
+    fail_point!("CCCryptorGCMAddAAD");
+    status(gcm(cryptorRef).and_then(|gcm| gcm.add_aad(input(aData, aDataLen)?)))
+}
+
+/// Encrypt data with a GCM `CCCryptorRef`. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMEncrypt(
+    cryptorRef: CCCryptorRef,
+    dataIn: *const u8,
+    dataInLength: usize,
+    dataOut: *mut u8,
+) -> c_int {
+    fail_point!("CCCryptorGCMEncrypt");
+    gcm_crypt(
+        CCOperation::kCCEncrypt,
+        cryptorRef,
+        dataIn,
+        dataInLength,
+        dataOut,
+    )
+}
+
+/// Decrypt data with a GCM `CCCryptorRef`. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMDecrypt(
+    cryptorRef: CCCryptorRef,
+    dataIn: *const u8,
+    dataInLength: usize,
+    dataOut: *mut u8,
+) -> c_int {
+    fail_point!("CCCryptorGCMDecrypt");
+    gcm_crypt(
+        CCOperation::kCCDecrypt,
+        cryptorRef,
+        dataIn,
+        dataInLength,
+        dataOut,
+    )
+}
+
+/// Produce the authentication tag of a GCM `CCCryptorRef`. The tag is not verified when
+/// decrypting. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMFinal(
+    cryptorRef: CCCryptorRef,
+    tagOut: *mut u8,
+    tagLength: *mut usize,
+) -> c_int {
+    fail_point!("CCCryptorGCMFinal");
+    status(gcm(cryptorRef).and_then(|gcm| {
+        let tag_length = tagLength.as_mut().ok_or(CCCryptorStatus::kCCParamError)?;
+        if *tag_length == 0 {
+            return Err(CCCryptorStatus::kCCParamError);
+        }
+        let tag = gcm.finish()?;
+        let len = cmp::min(*tag_length, tag.len());
+        output(tagOut, len)?.copy_from_slice(&tag[..len]);
+        *tag_length = len;
+        Ok(())
+    }))
+}
+
+/// Reset a GCM `CCCryptorRef` so that it can be used with a new initialization vector.
+/// Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMReset(cryptorRef: CCCryptorRef) -> c_int {
+    fail_point!("CCCryptorGCMReset");
+    status(gcm(cryptorRef).map(|gcm| gcm.reset()))
+}
+
+/// Runs a whole GCM operation, returning the authentication tag.
+#[allow(clippy::too_many_arguments)]
+unsafe fn gcm_oneshot(
+    op: CCOperation,
+    alg: c_uint,
+    key: *const u8,
+    key_length: usize,
+    iv: *const u8,
+    iv_length: usize,
+    a_data: *const u8,
+    a_data_length: usize,
+    data_in: *const u8,
+    data_in_length: usize,
+    data_out: *mut u8,
+    tag_length: usize,
+) -> Status<[u8; 16]> {
+    let alg = param(alg)?;
+    if alg != CCAlgorithm::kCCAlgorithmAES || tag_length == 0 || tag_length > 16 {
+        return Err(CCCryptorStatus::kCCParamError);
+    }
+    let mut gcm = Gcm::new(block_cipher(alg, input(key, key_length)?)?);
+    gcm.add_iv(input(iv, iv_length)?)?;
+    gcm.add_aad(input(a_data, a_data_length)?)?;
+    let mut data = input(data_in, data_in_length)?.to_vec();
+    gcm.crypt(op, &mut data)?;
+    output(data_out, data.len())?.copy_from_slice(&data);
+    gcm.finish()
+}
+
+/// Stateless, one-shot GCM encryption. Returns `0` on success.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMOneshotEncrypt(
+    alg: c_uint,
+    key: *const u8,
+    keyLength: usize,
+    iv: *const u8,
+    ivLength: usize,
+    aData: *const u8,
+    aDataLength: usize,
+    dataIn: *const u8,
+    dataInLength: usize,
+    dataOut: *mut u8,
+    tagOut: *mut u8,
+    tagLength: usize,
+) -> c_int {
+    fail_point!("CCCryptorGCMOneshotEncrypt");
+    status(
+        gcm_oneshot(
+            CCOperation::kCCEncrypt,
+            alg,
+            key,
+            keyLength,
+            iv,
+            ivLength,
+            aData,
+            aDataLength,
+            dataIn,
+            dataInLength,
+            dataOut,
+            tagLength,
+        )
+        .and_then(|tag| {
+            output(tagOut, tagLength)?.copy_from_slice(&tag[..tagLength]);
+            Ok(())
+        }),
+    )
+}
+
+/// Stateless, one-shot GCM decryption, which also verifies the authentication tag.
+/// Returns `0` on success.
+///
+/// If the tag does not match, the decrypted data is zeroed and `kCCDecodeError` is returned.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn CCCryptorGCMOneshotDecrypt(
+    alg: c_uint,
+    key: *const u8,
+    keyLength: usize,
+    iv: *const u8,
+    ivLength: usize,
+    aData: *const u8,
+    aDataLength: usize,
+    dataIn: *const u8,
+    dataInLength: usize,
+    dataOut: *mut u8,
+    tagIn: *const u8,
+    tagLength: usize,
+) -> c_int {
+    fail_point!("CCCryptorGCMOneshotDecrypt");
+    status(
+        gcm_oneshot(
+            CCOperation::kCCDecrypt,
+            alg,
+            key,
+            keyLength,
+            iv,
+            ivLength,
+            aData,
+            aDataLength,
+            dataIn,
+            dataInLength,
+            dataOut,
+            tagLength,
+        )
+        .and_then(|tag| {
+            let expected = input(tagIn, tagLength)?;
+            let difference = expected
+                .iter()
+                .zip(tag.iter())
+                .fold(0, |difference, (a, b)| difference | (a ^ b));
+            if difference == 0 {
+                Ok(())
+            } else {
+                for byte in output(dataOut, dataInLength)? {
+                    *byte = 0;
+                }
+                Err(CCCryptorStatus::kCCDecodeError)
+            }
+        }),
+    )
+}
+
+/// Stateless, one-shot encrypt or decrypt operation. Returns `0` on success.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn CCCrypt(
+    op: c_uint,
+    alg: c_uint,
+    options: CCOptions,
+    key: *const u8,
+    keyLength: usize,
+    iv: *const u8,
+    dataIn: *const u8,
+    dataInLength: usize,
+    dataOut: *mut u8,
+    dataOutAvailable: usize,
+    dataOutMoved: *mut usize,
+) -> c_int {
+    fail_point!("CCCrypt");
+    status(input(key, keyLength).and_then(|key| {
+        let alg = param(alg)?;
+        let (mode, padding) = mode_from_options(alg, options);
+        let mut cryptor = Cryptor::new(param(op)?, mode, alg, padding, iv, key)?;
+        let needed = cryptor.output_len(dataInLength, true);
+        if dataOutAvailable < needed {
+            set_moved(dataOutMoved, needed);
+            return Err(CCCryptorStatus::kCCBufferTooSmall);
+        }
+        let mut processed = cryptor.update(input(dataIn, dataInLength)?)?;
+        processed.extend(cryptor.finish()?);
+        output(dataOut, processed.len())?.copy_from_slice(&processed);
+        set_moved(dataOutMoved, processed.len());
+        Ok(())
+    }))
+}
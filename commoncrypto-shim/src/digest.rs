@@ -0,0 +1,232 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CCDigest*` functions.
+
+use commoncrypto_sys::{CCCryptorStatus, CCDigestAlgorithm, CCDigestCtx};
+use md2::Md2;
+use md4::Md4;
+use md5::Md5;
+use ripemd::{Ripemd128, Ripemd160, Ripemd256, Ripemd320};
+use sha1::Sha1;
+use sha2::digest::DynDigest;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use std::os::raw::{c_int, c_uint};
+use std::ptr;
+use {input, output, param, status, FromRaw, Status};
+
+/// Hasher behind a `CCDigestCtx` pointer.
+#[derive(Clone)]
+struct Digest {
+    algorithm: CCDigestAlgorithm,
+    digest: Box<dyn DynDigest>,
+    block_size: usize,
+}
+
+impl Digest {
+    /// Creates a hasher for `algorithm`, if it is supported.
+    fn new(algorithm: &CCDigestAlgorithm) -> Option<Digest> {
+        let (digest, block_size): (Box<dyn DynDigest>, usize) = match *algorithm {
+            CCDigestAlgorithm::kCCDigestMD2 => (Box::new(Md2::default()), 16),
+            CCDigestAlgorithm::kCCDigestMD4 => (Box::new(Md4::default()), 64),
+            CCDigestAlgorithm::kCCDigestMD5 => (Box::new(Md5::default()), 64),
+            CCDigestAlgorithm::kCCDigestRMD128 => (Box::new(Ripemd128::default()), 64),
+            CCDigestAlgorithm::kCCDigestRMD160 => (Box::new(Ripemd160::default()), 64),
+            CCDigestAlgorithm::kCCDigestRMD256 => (Box::new(Ripemd256::default()), 64),
+            CCDigestAlgorithm::kCCDigestRMD320 => (Box::new(Ripemd320::default()), 64),
+            CCDigestAlgorithm::kCCDigestSHA1 => (Box::new(Sha1::default()), 64),
+            CCDigestAlgorithm::kCCDigestSHA224 => (Box::new(Sha224::default()), 64),
+            CCDigestAlgorithm::kCCDigestSHA256 => (Box::new(Sha256::default()), 64),
+            CCDigestAlgorithm::kCCDigestSHA384 => (Box::new(Sha384::default()), 128),
+            CCDigestAlgorithm::kCCDigestSHA512 => (Box::new(Sha512::default()), 128),
+            // Skein is no longer supported by CommonCrypto either
+            _ => return None,
+        };
+        Some(Digest {
+            algorithm: *algorithm,
+            digest,
+            block_size,
+        })
+    }
+
+    /// Writes the digest of the data processed so far to `out`, and resets the hasher.
+    unsafe fn finish(&mut self, out: *mut u8) -> Status<()> {
+        let out = output(out, self.digest.output_size())?;
+        self.digest
+            .finalize_into_reset(out)
+            .map_err(|_| CCCryptorStatus::kCCBufferTooSmall)
+    }
+}
+
+unsafe fn digest<'a>(ctx: *mut CCDigestCtx) -> Status<&'a mut Digest> {
+    (ctx as *mut Digest)
+        .as_mut()
+        .ok_or(CCCryptorStatus::kCCParamError)
+}
+
+/// Generic digest hasher. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigest(
+    algorithm: c_uint,
+    data: *const u8,
+    length: usize,
+    out: *mut u8,
+) -> c_int {
+    fail_point!("CCDigest");
+    status(
+        param(algorithm)
+            .and_then(|algorithm| Digest::new(&algorithm).ok_or(CCCryptorStatus::kCCParamError))
+            .and_then(|mut digest| {
+                digest.digest.update(input(data, length)?);
+                digest.finish(out)
+            }),
+    )
+}
+
+/// Allocate and initialize a `CCDigestCtx` for a digest. Returns null if the algorithm is not
+/// supported.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestCreate(algorithm: c_uint) -> *mut CCDigestCtx {
+    if ::failure::injected("CCDigestCreate").is_some() {
+        return ptr::null_mut();
+    }
+    match CCDigestAlgorithm::from_raw(algorithm).and_then(|algorithm| Digest::new(&algorithm)) {
+        Some(digest) => Box::into_raw(Box::new(digest)) as *mut CCDigestCtx,
+        None => ptr::null_mut(),
+    }
+}
+
+/// Allocate and initialize a `CCDigestCtx` for the digest with the given DER-encoded object
+/// identifier. Returns null if there is no such digest.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestCreateByOID(oid: *const u8, oid_len: usize) -> *mut CCDigestCtx {
+    match input(oid, oid_len)
+        .ok()
+        .and_then(CCDigestAlgorithm::from_oid)
+    {
+        Some(algorithm) => CCDigestCreate(algorithm as c_uint),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Continue to digest data. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestUpdate(
+    ctx: *mut CCDigestCtx,
+    data: *const u8,
+    length: usize,
+) -> c_int {
+    fail_point!("CCDigestUpdate");
+    status(digest(ctx).and_then(|digest| {
+        digest.digest.update(input(data, length)?);
+        Ok(())
+    }))
+}
+
+/// Conclude digest operations and produce the digest output. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestFinal(ctx: *mut CCDigestCtx, out: *mut u8) -> c_int {
+    fail_point!("CCDigestFinal");
+    status(digest(ctx).and_then(|digest| digest.finish(out)))
+}
+
+/// Allocate a new `CCDigestCtx` which is a copy of the given one, including the data processed so
+/// far.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestClone(ctx: *mut CCDigestCtx) -> *mut CCDigestCtx {
+    if ::failure::injected("CCDigestClone").is_some() {
+        return ptr::null_mut();
+    }
+    match digest(ctx) {
+        Ok(digest) => Box::into_raw(Box::new(digest.clone())) as *mut CCDigestCtx,
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Clear and free a `CCDigestCtx`.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestDestroy(ctx: *mut CCDigestCtx) {
+    if !ctx.is_null() {
+        drop(Box::from_raw(ctx as *mut Digest));
+    }
+}
+
+/// Clear and re-initialize a `CCDigestCtx` for the same algorithm.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestReset(ctx: *mut CCDigestCtx) {
+    if let Ok(digest) = digest(ctx) {
+        digest.digest.reset();
+    }
+}
+
+/// Produce the digest output result for the bytes currently processed. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestGetDigest(ctx: *mut CCDigestCtx, out: *mut u8) -> c_int {
+    fail_point!("CCDigestGetDigest");
+    status(digest(ctx).and_then(|digest| digest.clone().finish(out)))
+}
+
+/// Provides the block size of the digest algorithm. Returns `0` on failure.
+#[no_mangle]
+pub extern "C" fn CCDigestGetBlockSize(algorithm: c_uint) -> usize {
+    CCDigestAlgorithm::from_raw(algorithm)
+        .and_then(|algorithm| Digest::new(&algorithm))
+        .map_or(0, |digest| digest.block_size)
+}
+
+/// Provides the digest output size of the digest algorithm. Returns `0` on failure.
+#[no_mangle]
+pub extern "C" fn CCDigestGetOutputSize(algorithm: c_uint) -> usize {
+    CCDigestAlgorithm::from_raw(algorithm)
+        .and_then(|algorithm| Digest::new(&algorithm))
+        .map_or(0, |digest| digest.digest.output_size())
+}
+
+/// Provides the block size of the digest algorithm. Returns `0` on failure.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestGetBlockSizeFromRef(ctx: *mut CCDigestCtx) -> usize {
+    digest(ctx).map_or(0, |digest| digest.block_size)
+}
+
+/// Provides the digest output size of the digest algorithm. Returns `0` on failure.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestGetOutputSizeFromRef(ctx: *mut CCDigestCtx) -> usize {
+    digest(ctx).map_or(0, |digest| digest.digest.output_size())
+}
+
+/// Provides the length of the DER-encoded object identifier of the digest algorithm. Returns `0`
+/// if it has none.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestOIDLen(ctx: *mut CCDigestCtx) -> usize {
+    digest(ctx)
+        .ok()
+        .and_then(|digest| digest.algorithm.oid())
+        .map_or(0, <[u8]>::len)
+}
+
+/// Writes the DER-encoded object identifier of the digest algorithm to `oid`.
+#[no_mangle]
+pub unsafe extern "C" fn CCDigestOID(ctx: *mut CCDigestCtx, oid: *mut u8) {
+    if let Some(expected) = digest(ctx).ok().and_then(|digest| digest.algorithm.oid()) {
+        if let Ok(oid) = output(oid, expected.len()) {
+            oid.copy_from_slice(expected);
+        }
+    }
+}
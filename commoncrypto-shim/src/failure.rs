@@ -0,0 +1,66 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Failure injection, so that error handling can be tested.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+
+thread_local! {
+    static FAILURES: RefCell<HashMap<String, c_int>> = RefCell::new(HashMap::new());
+}
+
+/// Makes every call to `function` on the current thread fail with `status`, until
+/// `clear_failures` is called.
+///
+/// Functions which return a status code return `status`. Functions which return a pointer
+/// (`CCDigestCreate` and `CCDigestClone`) return null, and functions which return nothing cannot
+/// fail.
+pub fn inject_failure(function: &str, status: c_int) {
+    FAILURES.with(|failures| {
+        failures.borrow_mut().insert(function.to_owned(), status);
+    });
+}
+
+/// Removes all of the failures injected on the current thread.
+pub fn clear_failures() {
+    FAILURES.with(|failures| failures.borrow_mut().clear());
+}
+
+/// The status injected for `function` on the current thread, if any.
+pub fn injected(function: &str) -> Option<c_int> {
+    FAILURES.with(|failures| failures.borrow().get(function).cloned())
+}
+
+/// C interface to `inject_failure`. `function` must be a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn CommonCryptoShimInjectFailure(function: *const c_char, status: c_int) {
+    if !function.is_null() {
+        inject_failure(&CStr::from_ptr(function).to_string_lossy(), status);
+    }
+}
+
+/// C interface to `clear_failures`.
+#[no_mangle]
+pub extern "C" fn CommonCryptoShimClearFailures() {
+    clear_failures();
+}
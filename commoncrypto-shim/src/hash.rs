@@ -0,0 +1,756 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CC_*` hash functions, whose contexts are allocated by callers.
+//!
+//! The hash state is kept in the fields of the context struct, as `CommonDigest.h` lays them out,
+//! so that contexts can be copied and discarded without being finalized, as they can with
+//! `CommonCrypto`, and so that their state can be exported.
+
+use commoncrypto_sys::{
+    CC_LONG, CC_MD2_CTX, CC_MD4_CTX, CC_MD5_CTX, CC_SHA256_CTX, CC_SHA512_CTX, CC_SHA_CTX,
+};
+use sha2::digest::generic_array::GenericArray;
+use std::os::raw::{c_int, c_uint};
+use std::ptr;
+use std::slice;
+use {input, output};
+
+/// Word of the internal state of a Merkle–Damgård hash function.
+pub trait Word: Copy + Default {
+    /// Number of bytes in a word.
+    const SIZE: usize;
+
+    fn from_u128(value: u128) -> Self;
+    fn to_u128(self) -> u128;
+    fn read_ne_bytes(bytes: &[u8]) -> Self;
+    fn write_ne_bytes(self, bytes: &mut [u8]);
+}
+
+impl Word for u32 {
+    const SIZE: usize = 4;
+
+    fn from_u128(value: u128) -> u32 {
+        value as u32
+    }
+
+    fn to_u128(self) -> u128 {
+        u128::from(self)
+    }
+
+    fn read_ne_bytes(bytes: &[u8]) -> u32 {
+        let mut word = [0; 4];
+        word.copy_from_slice(bytes);
+        u32::from_ne_bytes(word)
+    }
+
+    fn write_ne_bytes(self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+impl Word for u64 {
+    const SIZE: usize = 8;
+
+    fn from_u128(value: u128) -> u64 {
+        value as u64
+    }
+
+    fn to_u128(self) -> u128 {
+        u128::from(self)
+    }
+
+    fn read_ne_bytes(bytes: &[u8]) -> u64 {
+        let mut word = [0; 8];
+        word.copy_from_slice(bytes);
+        u64::from_ne_bytes(word)
+    }
+
+    fn write_ne_bytes(self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&self.to_ne_bytes());
+    }
+}
+
+/// Parameters of a Merkle–Damgård hash function, with `N` words of state and `B`-byte blocks.
+pub struct Params<W, const N: usize, const B: usize> {
+    initial: [W; N],
+    compress: fn(&mut [W; N], &[u8; B]),
+    /// Whether lengths and state words are little-endian (MD5) rather than big-endian (SHA).
+    little_endian: bool,
+    pub output_size: usize,
+}
+
+/// Hash state: the chaining words, the number of bytes hashed, and the partial block.
+#[derive(Clone, Copy)]
+pub struct Context<W, const N: usize, const B: usize> {
+    state: [W; N],
+    count: u128,
+    buffer: [u8; B],
+}
+
+impl<W: Word, const N: usize, const B: usize> Context<W, N, B> {
+    pub fn new(params: &Params<W, N, B>) -> Context<W, N, B> {
+        Context {
+            state: params.initial,
+            count: 0,
+            buffer: [0; B],
+        }
+    }
+
+    fn count(&self) -> u128 {
+        self.count
+    }
+
+    pub fn update(&mut self, params: &Params<W, N, B>, mut data: &[u8]) {
+        let count = self.count();
+        let mut used = (count % B as u128) as usize;
+        self.count = count.wrapping_add(data.len() as u128);
+        while !data.is_empty() {
+            let len = (B - used).min(data.len());
+            self.buffer[used..used + len].copy_from_slice(&data[..len]);
+            used += len;
+            data = &data[len..];
+            if used == B {
+                (params.compress)(&mut self.state, &self.buffer);
+                used = 0;
+            }
+        }
+    }
+
+    /// Writes the hash to `out`, which must be `params.output_size` bytes long.
+    pub fn finish(mut self, params: &Params<W, N, B>, out: &mut [u8]) {
+        let length_size = 2 * W::SIZE;
+        let bits = self.count().wrapping_mul(8);
+        let mut length = [0u8; 16];
+        if params.little_endian {
+            length.copy_from_slice(&bits.to_le_bytes());
+        } else {
+            length[..length_size].copy_from_slice(&bits.to_be_bytes()[16 - length_size..]);
+        }
+        let used = (self.count() % B as u128) as usize;
+        let padding = if used < B - length_size {
+            B - length_size - used
+        } else {
+            2 * B - length_size - used
+        };
+        let mut trailer = vec![0u8; padding + length_size];
+        trailer[0] = 0x80;
+        trailer[padding..].copy_from_slice(&length[..length_size]);
+        self.update(params, &trailer);
+
+        let mut digest = Vec::with_capacity(N * W::SIZE);
+        for word in self.state.iter() {
+            let value = word.to_u128();
+            if params.little_endian {
+                digest.extend_from_slice(&value.to_le_bytes()[..W::SIZE]);
+            } else {
+                digest.extend_from_slice(&value.to_be_bytes()[16 - W::SIZE..]);
+            }
+        }
+        out.copy_from_slice(&digest[..params.output_size]);
+    }
+}
+
+/// MD4 and MD5 context.
+pub type Md5 = Context<u32, 4, 64>;
+/// SHA-1 context.
+pub type Sha1 = Context<u32, 5, 64>;
+/// SHA-224 and SHA-256 context.
+pub type Sha256 = Context<u32, 8, 64>;
+/// SHA-384 and SHA-512 context.
+pub type Sha512 = Context<u64, 8, 128>;
+
+/// MD2 context, as specified in RFC 1319. MD2 is not a Merkle–Damgård hash function, so it does
+/// not use `Context`.
+pub struct Md2 {
+    state: [u8; 48],
+    checksum: [u8; 16],
+    buffer: [u8; 16],
+    used: u32,
+}
+
+/// Parameters of MD2, which has none besides its output size.
+pub struct Md2Params {
+    pub output_size: usize,
+}
+
+pub const MD2: Md2Params = Md2Params { output_size: 16 };
+
+/// Permutation of 0..255 constructed from the digits of pi, from RFC 1319, section 3.2.
+const MD2_S: [u8; 256] = [
+    41, 46, 67, 201, 162, 216, 124, 1, 61, 54, 84, 161, 236, 240, 6, 19, 98, 167, 5, 243, 192, 199,
+    115, 140, 152, 147, 43, 217, 188, 76, 130, 202, 30, 155, 87, 60, 253, 212, 224, 22, 103, 66,
+    111, 24, 138, 23, 229, 18, 190, 78, 196, 214, 218, 158, 222, 73, 160, 251, 245, 142, 187, 47,
+    238, 122, 169, 104, 121, 145, 21, 178, 7, 63, 148, 194, 16, 137, 11, 34, 95, 33, 128, 127, 93,
+    154, 90, 144, 50, 39, 53, 62, 204, 231, 191, 247, 151, 3, 255, 25, 48, 179, 72, 165, 181, 209,
+    215, 94, 146, 42, 172, 86, 170, 198, 79, 184, 56, 210, 150, 164, 125, 182, 118, 252, 107, 226,
+    156, 116, 4, 241, 69, 157, 112, 89, 100, 113, 135, 32, 134, 91, 207, 101, 230, 45, 168, 2, 27,
+    96, 37, 173, 174, 176, 185, 246, 28, 70, 97, 105, 52, 64, 126, 15, 85, 71, 163, 35, 221, 81,
+    175, 58, 195, 92, 249, 206, 186, 197, 234, 38, 44, 83, 13, 110, 133, 40, 132, 9, 211, 223, 205,
+    244, 65, 129, 77, 82, 106, 220, 55, 200, 108, 193, 171, 250, 36, 225, 123, 8, 12, 189, 177, 74,
+    120, 136, 149, 139, 227, 99, 232, 109, 233, 203, 213, 254, 59, 0, 29, 57, 242, 239, 183, 14,
+    102, 88, 208, 228, 166, 119, 114, 248, 235, 117, 75, 10, 49, 68, 80, 180, 143, 237, 31, 26,
+    219, 153, 141, 51, 159, 17, 131, 20,
+];
+
+impl Md2 {
+    pub fn new(_: &Md2Params) -> Md2 {
+        Md2 {
+            state: [0; 48],
+            checksum: [0; 16],
+            buffer: [0; 16],
+            used: 0,
+        }
+    }
+
+    fn compress(&mut self, block: &[u8; 16]) {
+        for (j, byte) in block.iter().enumerate() {
+            self.state[16 + j] = *byte;
+            self.state[32 + j] = *byte ^ self.state[j];
+        }
+        let mut t = 0u8;
+        for j in 0..18u8 {
+            for byte in self.state.iter_mut() {
+                *byte ^= MD2_S[t as usize];
+                t = *byte;
+            }
+            t = t.wrapping_add(j);
+        }
+        let mut l = self.checksum[15];
+        for (checksum, byte) in self.checksum.iter_mut().zip(block.iter()) {
+            *checksum ^= MD2_S[(byte ^ l) as usize];
+            l = *checksum;
+        }
+    }
+
+    pub fn update(&mut self, _: &Md2Params, data: &[u8]) {
+        for byte in data {
+            self.buffer[self.used as usize] = *byte;
+            self.used += 1;
+            if self.used == 16 {
+                let block = self.buffer;
+                self.compress(&block);
+                self.used = 0;
+            }
+        }
+    }
+
+    pub fn finish(&mut self, params: &Md2Params, out: &mut [u8]) {
+        let padding = 16 - self.used as u8;
+        self.update(params, &[padding; 16][..padding as usize]);
+        let checksum = self.checksum;
+        self.compress(&checksum);
+        out.copy_from_slice(&self.state[..params.output_size]);
+    }
+}
+
+const MD4_ROUND_2: [usize; 16] = [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+const MD4_ROUND_3: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+const MD4_SHIFTS: [u32; 12] = [3, 7, 11, 19, 3, 5, 9, 13, 3, 9, 11, 15];
+
+/// MD4 compression function, as specified in RFC 1320, section 3.4.
+fn md4_compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let mut x = [0u32; 16];
+    for (word, bytes) in x.iter_mut().zip(block.chunks(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..48 {
+        let (f, k, constant) = match i / 16 {
+            0 => ((b & c) | (!b & d), i, 0),
+            1 => (
+                (b & c) | (b & d) | (c & d),
+                MD4_ROUND_2[i % 16],
+                0x5a82_7999,
+            ),
+            _ => (b ^ c ^ d, MD4_ROUND_3[i % 16], 0x6ed9_eba1),
+        };
+        let rotated = a
+            .wrapping_add(f)
+            .wrapping_add(x[k])
+            .wrapping_add(constant)
+            .rotate_left(MD4_SHIFTS[i / 16 * 4 + i % 4]);
+        a = d;
+        d = c;
+        c = b;
+        b = rotated;
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d].iter()) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+const MD5_K: [u32; 64] = [
+    0xd76a_a478,
+    0xe8c7_b756,
+    0x2420_70db,
+    0xc1bd_ceee,
+    0xf57c_0faf,
+    0x4787_c62a,
+    0xa830_4613,
+    0xfd46_9501,
+    0x6980_98d8,
+    0x8b44_f7af,
+    0xffff_5bb1,
+    0x895c_d7be,
+    0x6b90_1122,
+    0xfd98_7193,
+    0xa679_438e,
+    0x49b4_0821,
+    0xf61e_2562,
+    0xc040_b340,
+    0x265e_5a51,
+    0xe9b6_c7aa,
+    0xd62f_105d,
+    0x0244_1453,
+    0xd8a1_e681,
+    0xe7d3_fbc8,
+    0x21e1_cde6,
+    0xc337_07d6,
+    0xf4d5_0d87,
+    0x455a_14ed,
+    0xa9e3_e905,
+    0xfcef_a3f8,
+    0x676f_02d9,
+    0x8d2a_4c8a,
+    0xfffa_3942,
+    0x8771_f681,
+    0x6d9d_6122,
+    0xfde5_380c,
+    0xa4be_ea44,
+    0x4bde_cfa9,
+    0xf6bb_4b60,
+    0xbebf_bc70,
+    0x289b_7ec6,
+    0xeaa1_27fa,
+    0xd4ef_3085,
+    0x0488_1d05,
+    0xd9d4_d039,
+    0xe6db_99e5,
+    0x1fa2_7cf8,
+    0xc4ac_5665,
+    0xf429_2244,
+    0x432a_ff97,
+    0xab94_23a7,
+    0xfc93_a039,
+    0x655b_59c3,
+    0x8f0c_cc92,
+    0xffef_f47d,
+    0x8584_5dd1,
+    0x6fa8_7e4f,
+    0xfe2c_e6e0,
+    0xa301_4314,
+    0x4e08_11a1,
+    0xf753_7e82,
+    0xbd3a_f235,
+    0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+const MD5_SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+/// MD5 compression function, as specified in RFC 1321, section 3.4.
+fn md5_compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let mut m = [0u32; 16];
+    for (word, bytes) in m.iter_mut().zip(block.chunks(4)) {
+        *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let rotated = a
+            .wrapping_add(f)
+            .wrapping_add(MD5_K[i])
+            .wrapping_add(m[g])
+            .rotate_left(MD5_SHIFTS[i / 16 * 4 + i % 4]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(rotated);
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d].iter()) {
+        *word = word.wrapping_add(*value);
+    }
+}
+
+fn sha1_compress(state: &mut [u32; 5], block: &[u8; 64]) {
+    sha1::compress(state, slice::from_ref(GenericArray::from_slice(block)));
+}
+
+fn sha256_compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    sha2::compress256(state, slice::from_ref(GenericArray::from_slice(block)));
+}
+
+fn sha512_compress(state: &mut [u64; 8], block: &[u8; 128]) {
+    sha2::compress512(state, slice::from_ref(GenericArray::from_slice(block)));
+}
+
+pub const MD4: Params<u32, 4, 64> = Params {
+    initial: [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+    compress: md4_compress,
+    little_endian: true,
+    output_size: 16,
+};
+
+pub const MD5: Params<u32, 4, 64> = Params {
+    initial: [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+    compress: md5_compress,
+    little_endian: true,
+    output_size: 16,
+};
+
+pub const SHA1: Params<u32, 5, 64> = Params {
+    initial: [
+        0x6745_2301,
+        0xefcd_ab89,
+        0x98ba_dcfe,
+        0x1032_5476,
+        0xc3d2_e1f0,
+    ],
+    compress: sha1_compress,
+    little_endian: false,
+    output_size: 20,
+};
+
+pub const SHA224: Params<u32, 8, 64> = Params {
+    initial: [
+        0xc105_9ed8,
+        0x367c_d507,
+        0x3070_dd17,
+        0xf70e_5939,
+        0xffc0_0b31,
+        0x6858_1511,
+        0x64f9_8fa7,
+        0xbefa_4fa4,
+    ],
+    compress: sha256_compress,
+    little_endian: false,
+    output_size: 28,
+};
+
+pub const SHA256: Params<u32, 8, 64> = Params {
+    initial: [
+        0x6a09_e667,
+        0xbb67_ae85,
+        0x3c6e_f372,
+        0xa54f_f53a,
+        0x510e_527f,
+        0x9b05_688c,
+        0x1f83_d9ab,
+        0x5be0_cd19,
+    ],
+    compress: sha256_compress,
+    little_endian: false,
+    output_size: 32,
+};
+
+pub const SHA384: Params<u64, 8, 128> = Params {
+    initial: [
+        0xcbbb_9d5d_c105_9ed8,
+        0x629a_292a_367c_d507,
+        0x9159_015a_3070_dd17,
+        0x152f_ecd8_f70e_5939,
+        0x6733_2667_ffc0_0b31,
+        0x8eb4_4a87_6858_1511,
+        0xdb0c_2e0d_64f9_8fa7,
+        0x47b5_481d_befa_4fa4,
+    ],
+    compress: sha512_compress,
+    little_endian: false,
+    output_size: 48,
+};
+
+pub const SHA512: Params<u64, 8, 128> = Params {
+    initial: [
+        0x6a09_e667_f3bc_c908,
+        0xbb67_ae85_84ca_a73b,
+        0x3c6e_f372_fe94_f82b,
+        0xa54f_f53a_5f1d_36f1,
+        0x510e_527f_ade6_82d1,
+        0x9b05_688c_2b3e_6c1f,
+        0x1f83_d9ab_fb41_bd6b,
+        0x5be0_cd19_137e_2179,
+    ],
+    compress: sha512_compress,
+    little_endian: false,
+    output_size: 64,
+};
+
+/// Hash state which is kept in the fields of the `CommonCrypto` context struct `C`.
+pub trait Stored<C>: Sized {
+    fn load(ctx: &C) -> Self;
+    fn store(&self, ctx: &mut C);
+}
+
+/// Reads the partial block from the words of a context struct, in memory order.
+fn load_block<W: Word, const B: usize>(words: &[W]) -> [u8; B] {
+    let mut block = [0; B];
+    for (bytes, word) in block.chunks_mut(W::SIZE).zip(words.iter()) {
+        word.write_ne_bytes(bytes);
+    }
+    block
+}
+
+/// Writes the partial block into the words of a context struct, in memory order.
+fn store_block<W: Word>(block: &[u8], words: &mut [W]) {
+    for (word, bytes) in words.iter_mut().zip(block.chunks(W::SIZE)) {
+        *word = W::read_ne_bytes(bytes);
+    }
+}
+
+/// MD4, MD5 and SHA-1 contexts count the bits hashed, and the bytes in the partial block.
+macro_rules! stored_md {
+    ($cc_ctx: ty, $ctx: ty, $($word: ident),+) => {
+        impl Stored<$cc_ctx> for $ctx {
+            fn load(ctx: &$cc_ctx) -> $ctx {
+                Context {
+                    state: [$(ctx.$word),+],
+                    count: (u128::from(ctx.Nh) << 32 | u128::from(ctx.Nl)) / 8,
+                    buffer: load_block(&ctx.data),
+                }
+            }
+
+            fn store(&self, ctx: &mut $cc_ctx) {
+                let mut words = self.state.iter();
+                $(ctx.$word = *words.next().unwrap();)+
+                let bits = self.count.wrapping_mul(8);
+                ctx.Nl = bits as c_uint;
+                ctx.Nh = (bits >> 32) as c_uint;
+                store_block(&self.buffer, &mut ctx.data);
+                ctx.num = (self.count % 64) as c_uint;
+            }
+        }
+    };
+}
+
+/// SHA-2 contexts count the bytes hashed, which determine the bytes in the partial block.
+macro_rules! stored_sha2 {
+    ($cc_ctx: ty, $ctx: ty, $word: ty) => {
+        impl Stored<$cc_ctx> for $ctx {
+            fn load(ctx: &$cc_ctx) -> $ctx {
+                Context {
+                    state: ctx.hash,
+                    count: ctx.count[0].to_u128() | ctx.count[1].to_u128() << (<$word>::SIZE * 8),
+                    buffer: load_block(&ctx.wbuf),
+                }
+            }
+
+            fn store(&self, ctx: &mut $cc_ctx) {
+                ctx.hash = self.state;
+                ctx.count = [
+                    <$word>::from_u128(self.count),
+                    <$word>::from_u128(self.count >> (<$word>::SIZE * 8)),
+                ];
+                store_block(&self.buffer, &mut ctx.wbuf);
+            }
+        }
+    };
+}
+
+stored_md!(CC_MD4_CTX, Md5, A, B, C, D);
+stored_md!(CC_MD5_CTX, Md5, A, B, C, D);
+stored_md!(CC_SHA_CTX, Sha1, h0, h1, h2, h3, h4);
+stored_sha2!(CC_SHA256_CTX, Sha256, u32);
+stored_sha2!(CC_SHA512_CTX, Sha512, u64);
+
+/// MD2 contexts keep one byte per word. Only the first 16 bytes of the state are carried between
+/// blocks.
+impl Stored<CC_MD2_CTX> for Md2 {
+    fn load(ctx: &CC_MD2_CTX) -> Md2 {
+        let mut md2 = Md2::new(&MD2);
+        for (byte, word) in md2.state.iter_mut().zip(ctx.state.iter()) {
+            *byte = *word as u8;
+        }
+        for (byte, word) in md2.checksum.iter_mut().zip(ctx.cksm.iter()) {
+            *byte = *word as u8;
+        }
+        md2.buffer = ctx.data;
+        md2.used = ctx.num as u32 % 16;
+        md2
+    }
+
+    fn store(&self, ctx: &mut CC_MD2_CTX) {
+        for (word, byte) in ctx.state.iter_mut().zip(self.state.iter()) {
+            *word = c_uint::from(*byte);
+        }
+        for (word, byte) in ctx.cksm.iter_mut().zip(self.checksum.iter()) {
+            *word = c_uint::from(*byte);
+        }
+        ctx.data = self.buffer;
+        ctx.num = self.used as c_int;
+    }
+}
+
+macro_rules! cc_hash {
+    (
+        $cc_ctx: ty,
+        $ctx: ty,
+        $params: expr,
+        $init_func: ident,
+        $update_func: ident,
+        $final_func: ident,
+        $oneshot_func: ident
+    ) => {
+        /// Initializes the hasher. Returns `1` on success.
+        #[no_mangle]
+        pub unsafe extern "C" fn $init_func(ctx: *mut $cc_ctx) -> c_int {
+            fail_point!(stringify!($init_func));
+            match ctx.as_mut() {
+                Some(ctx) => {
+                    <$ctx>::new(&$params).store(ctx);
+                    1
+                }
+                None => 0,
+            }
+        }
+
+        /// Appends data to be hashed. Returns `1` on success.
+        #[no_mangle]
+        pub unsafe extern "C" fn $update_func(
+            ctx: *mut $cc_ctx,
+            data: *const u8,
+            n: CC_LONG,
+        ) -> c_int {
+            fail_point!(stringify!($update_func));
+            match (ctx.as_mut(), input(data, n as usize)) {
+                (Some(ctx), Ok(data)) => {
+                    let mut state = <$ctx>::load(ctx);
+                    state.update(&$params, data);
+                    state.store(ctx);
+                    1
+                }
+                _ => 0,
+            }
+        }
+
+        /// Generates the hash, and clears the context. Returns `1` on success.
+        #[no_mangle]
+        pub unsafe extern "C" fn $final_func(md: *mut u8, ctx: *mut $cc_ctx) -> c_int {
+            fail_point!(stringify!($final_func));
+            match (ctx.as_mut(), output(md, $params.output_size)) {
+                (Some(ctx), Ok(md)) => {
+                    <$ctx>::load(ctx).finish(&$params, md);
+                    *ctx = <$cc_ctx>::default();
+                    1
+                }
+                _ => 0,
+            }
+        }
+
+        /// Generates the hash of `n` bytes of `data` into `md`. Returns `md`, or null on failure.
+        #[no_mangle]
+        pub unsafe extern "C" fn $oneshot_func(
+            data: *const u8,
+            n: CC_LONG,
+            md: *mut u8,
+        ) -> *mut u8 {
+            if ::failure::injected(stringify!($oneshot_func)).is_some() {
+                return ptr::null_mut();
+            }
+            let mut ctx = <$cc_ctx>::default();
+            if $init_func(&mut ctx) == 1
+                && $update_func(&mut ctx, data, n) == 1
+                && $final_func(md, &mut ctx) == 1
+            {
+                md
+            } else {
+                ptr::null_mut()
+            }
+        }
+    };
+}
+
+cc_hash!(
+    CC_MD2_CTX,
+    Md2,
+    MD2,
+    CC_MD2_Init,
+    CC_MD2_Update,
+    CC_MD2_Final,
+    CC_MD2
+);
+cc_hash!(
+    CC_MD4_CTX,
+    Md5,
+    MD4,
+    CC_MD4_Init,
+    CC_MD4_Update,
+    CC_MD4_Final,
+    CC_MD4
+);
+cc_hash!(
+    CC_MD5_CTX,
+    Md5,
+    MD5,
+    CC_MD5_Init,
+    CC_MD5_Update,
+    CC_MD5_Final,
+    CC_MD5
+);
+cc_hash!(
+    CC_SHA_CTX,
+    Sha1,
+    SHA1,
+    CC_SHA1_Init,
+    CC_SHA1_Update,
+    CC_SHA1_Final,
+    CC_SHA1
+);
+cc_hash!(
+    CC_SHA256_CTX,
+    Sha256,
+    SHA224,
+    CC_SHA224_Init,
+    CC_SHA224_Update,
+    CC_SHA224_Final,
+    CC_SHA224
+);
+cc_hash!(
+    CC_SHA256_CTX,
+    Sha256,
+    SHA256,
+    CC_SHA256_Init,
+    CC_SHA256_Update,
+    CC_SHA256_Final,
+    CC_SHA256
+);
+cc_hash!(
+    CC_SHA512_CTX,
+    Sha512,
+    SHA384,
+    CC_SHA384_Init,
+    CC_SHA384_Update,
+    CC_SHA384_Final,
+    CC_SHA384
+);
+cc_hash!(
+    CC_SHA512_CTX,
+    Sha512,
+    SHA512,
+    CC_SHA512_Init,
+    CC_SHA512_Update,
+    CC_SHA512_Final,
+    CC_SHA512
+);
@@ -0,0 +1,231 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CCHmac*` functions.
+
+use commoncrypto_sys::{CCHmacAlgorithm, CCHmacContext};
+use hash::{self, Md5, Sha1, Sha256, Sha512};
+use std::mem;
+use std::os::raw::c_uint;
+use std::ptr;
+use {input, output, FromRaw};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+/// Marks a `CCHmacContext` which has been initialized.
+const MAGIC: u32 = 0x484d_4143;
+
+/// Hash state of one of the algorithms supported by HMAC.
+#[derive(Clone, Copy)]
+enum Engine {
+    Md5(Md5),
+    Sha1(Sha1),
+    Sha224(Sha256),
+    Sha256(Sha256),
+    Sha384(Sha512),
+    Sha512(Sha512),
+}
+
+impl Engine {
+    fn new(algorithm: CCHmacAlgorithm) -> Engine {
+        match algorithm {
+            CCHmacAlgorithm::kCCHmacAlgMD5 => Engine::Md5(Md5::new(&hash::MD5)),
+            CCHmacAlgorithm::kCCHmacAlgSHA1 => Engine::Sha1(Sha1::new(&hash::SHA1)),
+            CCHmacAlgorithm::kCCHmacAlgSHA224 => Engine::Sha224(Sha256::new(&hash::SHA224)),
+            CCHmacAlgorithm::kCCHmacAlgSHA256 => Engine::Sha256(Sha256::new(&hash::SHA256)),
+            CCHmacAlgorithm::kCCHmacAlgSHA384 => Engine::Sha384(Sha512::new(&hash::SHA384)),
+            CCHmacAlgorithm::kCCHmacAlgSHA512 => Engine::Sha512(Sha512::new(&hash::SHA512)),
+        }
+    }
+
+    fn block_size(&self) -> usize {
+        match *self {
+            Engine::Sha384(_) | Engine::Sha512(_) => 128,
+            _ => 64,
+        }
+    }
+
+    fn output_size(&self) -> usize {
+        match *self {
+            Engine::Md5(_) => hash::MD5.output_size,
+            Engine::Sha1(_) => hash::SHA1.output_size,
+            Engine::Sha224(_) => hash::SHA224.output_size,
+            Engine::Sha256(_) => hash::SHA256.output_size,
+            Engine::Sha384(_) => hash::SHA384.output_size,
+            Engine::Sha512(_) => hash::SHA512.output_size,
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match *self {
+            Engine::Md5(ref mut ctx) => ctx.update(&hash::MD5, data),
+            Engine::Sha1(ref mut ctx) => ctx.update(&hash::SHA1, data),
+            Engine::Sha224(ref mut ctx) => ctx.update(&hash::SHA224, data),
+            Engine::Sha256(ref mut ctx) => ctx.update(&hash::SHA256, data),
+            Engine::Sha384(ref mut ctx) => ctx.update(&hash::SHA384, data),
+            Engine::Sha512(ref mut ctx) => ctx.update(&hash::SHA512, data),
+        }
+    }
+
+    /// Writes the hash to `out`, which must be `output_size()` bytes long.
+    fn finish(self, out: &mut [u8]) {
+        match self {
+            Engine::Md5(ctx) => ctx.finish(&hash::MD5, out),
+            Engine::Sha1(ctx) => ctx.finish(&hash::SHA1, out),
+            Engine::Sha224(ctx) => ctx.finish(&hash::SHA224, out),
+            Engine::Sha256(ctx) => ctx.finish(&hash::SHA256, out),
+            Engine::Sha384(ctx) => ctx.finish(&hash::SHA384, out),
+            Engine::Sha512(ctx) => ctx.finish(&hash::SHA512, out),
+        }
+    }
+}
+
+/// Keyed HMAC state, stored inline in a `CCHmacContext`: the inner hash primed with the padded
+/// key, and the padded key for the outer hash.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct HmacState {
+    magic: u32,
+    algorithm: CCHmacAlgorithm,
+    inner: Engine,
+    outer_key: [u8; 128],
+}
+
+const _: () = assert!(mem::size_of::<HmacState>() <= mem::size_of::<CCHmacContext>());
+
+impl HmacState {
+    pub fn new(algorithm: CCHmacAlgorithm, key: &[u8]) -> HmacState {
+        let mut inner = Engine::new(algorithm);
+        let block_size = inner.block_size();
+        let mut padded_key = [0u8; 128];
+        if key.len() > block_size {
+            let mut hasher = inner;
+            hasher.update(key);
+            hasher.finish(&mut padded_key[..inner.output_size()]);
+        } else {
+            padded_key[..key.len()].copy_from_slice(key);
+        }
+        let mut inner_key = padded_key;
+        let mut outer_key = padded_key;
+        for (i, o) in inner_key.iter_mut().zip(outer_key.iter_mut()) {
+            *i ^= IPAD;
+            *o ^= OPAD;
+        }
+        inner.update(&inner_key[..block_size]);
+        HmacState {
+            magic: MAGIC,
+            algorithm,
+            inner,
+            outer_key,
+        }
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.inner.output_size()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Writes the message authentication code to `mac`, which must be `output_size()` bytes long.
+    pub fn finish(self, mac: &mut [u8]) {
+        let mut inner_hash = [0u8; 64];
+        let inner_hash = &mut inner_hash[..self.output_size()];
+        self.inner.finish(inner_hash);
+        let mut outer = Engine::new(self.algorithm);
+        outer.update(&self.outer_key[..outer.block_size()]);
+        outer.update(inner_hash);
+        outer.finish(mac);
+    }
+
+    /// Reads the state from `ctx`, if it has been initialized.
+    unsafe fn load(ctx: *const CCHmacContext) -> Option<HmacState> {
+        if ctx.is_null() || ptr::read_unaligned(ctx as *const u32) != MAGIC {
+            return None;
+        }
+        // CCHmacContext is only 4-byte aligned
+        Some(ptr::read_unaligned(ctx as *const HmacState))
+    }
+
+    unsafe fn store(self, ctx: *mut CCHmacContext) {
+        ptr::write_unaligned(ctx as *mut HmacState, self);
+    }
+}
+
+/// Initialize a `CCHmacContext` with the provided key and algorithm.
+#[no_mangle]
+pub unsafe extern "C" fn CCHmacInit(
+    ctx: *mut CCHmacContext,
+    algorithm: c_uint,
+    key: *const u8,
+    keyLength: usize,
+) {
+    if let (false, Some(algorithm), Ok(key)) = (
+        ctx.is_null(),
+        CCHmacAlgorithm::from_raw(algorithm),
+        input(key, keyLength),
+    ) {
+        HmacState::new(algorithm, key).store(ctx);
+    }
+}
+
+/// Process some data.
+#[no_mangle]
+pub unsafe extern "C" fn CCHmacUpdate(ctx: *mut CCHmacContext, data: *const u8, dataLength: usize) {
+    if let (Some(mut state), Ok(data)) = (HmacState::load(ctx), input(data, dataLength)) {
+        state.update(data);
+        state.store(ctx);
+    }
+}
+
+/// Obtain the final Message Authentication Code, and clear the context.
+#[no_mangle]
+pub unsafe extern "C" fn CCHmacFinal(ctx: *mut CCHmacContext, macOut: *mut u8) {
+    if let Some(state) = HmacState::load(ctx) {
+        if let Ok(mac) = output(macOut, state.output_size()) {
+            state.finish(mac);
+        }
+        ptr::write_bytes(ctx, 0, 1);
+    }
+}
+
+/// Stateless, one-shot HMAC function.
+#[no_mangle]
+pub unsafe extern "C" fn CCHmac(
+    algorithm: c_uint,
+    key: *const u8,
+    keyLength: usize,
+    data: *const u8,
+    dataLength: usize,
+    macOut: *mut u8,
+) {
+    if let (Some(algorithm), Ok(key), Ok(data)) = (
+        CCHmacAlgorithm::from_raw(algorithm),
+        input(key, keyLength),
+        input(data, dataLength),
+    ) {
+        let mut state = HmacState::new(algorithm, key);
+        state.update(data);
+        if let Ok(mac) = output(macOut, state.output_size()) {
+            state.finish(mac);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CommonCrypto` C ABI, for platforms other than Apple's.
+//!
+//! This crate exports the functions declared in `commoncrypto-sys`, with the same struct layouts
+//! and status codes, so that code using `CommonCrypto` can be built and tested on other
+//! platforms. It can be linked into Rust code (`extern crate commoncrypto_shim;`), or built as a
+//! `cdylib` or `staticlib` for use from other languages.
+//!
+//! Failures can be injected into the exported functions with `inject_failure`, so that error
+//! handling can be tested.
+
+#![warn(missing_docs)]
+#![allow(non_snake_case)]
+
+extern crate aes;
+extern crate blowfish;
+extern crate cast5;
+extern crate commoncrypto_sys;
+extern crate des;
+extern crate getrandom;
+extern crate ghash;
+extern crate md2;
+extern crate md4;
+extern crate md5;
+extern crate rc2;
+extern crate ripemd;
+extern crate sha1;
+extern crate sha2;
+
+use commoncrypto_sys::{
+    CCAlgorithm, CCCryptorStatus, CCDigestAlgorithm, CCHmacAlgorithm, CCMode, CCOperation,
+    CCPBKDFAlgorithm, CCPadding, CCPseudoRandomAlgorithm,
+};
+use std::os::raw::{c_int, c_uint};
+use std::result;
+use std::slice;
+
+/// Returns the injected status from the enclosing function, if there is one for `$function`.
+macro_rules! fail_point {
+    ($function: expr) => {
+        if let Some(status) = ::failure::injected($function) {
+            return status;
+        }
+    };
+}
+
+mod cryptor;
+mod digest;
+mod failure;
+mod hash;
+mod hmac;
+mod pbkdf2;
+mod random;
+
+pub use failure::{clear_failures, inject_failure};
+
+type Status<T> = result::Result<T, CCCryptorStatus>;
+
+/// Conversion from the raw value of a C enum, which may be out of range, to the Rust enum.
+///
+/// The exported functions take enums as raw values, because an out-of-range Rust enum is undefined
+/// behavior.
+trait FromRaw: Sized {
+    /// The variant whose value is `value`, if there is one.
+    fn from_raw(value: c_uint) -> Option<Self>;
+}
+
+macro_rules! from_raw {
+    ($($enum: ident { $($variant: ident),+ })+) => {
+        $(
+            impl FromRaw for $enum {
+                fn from_raw(value: c_uint) -> Option<$enum> {
+                    [$($enum::$variant),+]
+                        .iter()
+                        .cloned()
+                        .find(|variant| *variant as c_uint == value)
+                }
+            }
+        )+
+    };
+}
+
+from_raw! {
+    CCAlgorithm {
+        kCCAlgorithmAES, kCCAlgorithmDES, kCCAlgorithm3DES, kCCAlgorithmCAST, kCCAlgorithmRC4,
+        kCCAlgorithmRC2, kCCAlgorithmBlowfish
+    }
+    CCDigestAlgorithm {
+        kCCDigestNone, kCCDigestMD2, kCCDigestMD4, kCCDigestMD5, kCCDigestRMD128,
+        kCCDigestRMD160, kCCDigestRMD256, kCCDigestRMD320, kCCDigestSHA1, kCCDigestSHA224,
+        kCCDigestSHA256, kCCDigestSHA384, kCCDigestSHA512, kCCDigestSkein128, kCCDigestSkein160,
+        kCCDigestSkein224, kCCDigestSkein256, kCCDigestSkein384, kCCDigestSkein512
+    }
+    CCHmacAlgorithm {
+        kCCHmacAlgSHA1, kCCHmacAlgMD5, kCCHmacAlgSHA256, kCCHmacAlgSHA384, kCCHmacAlgSHA512,
+        kCCHmacAlgSHA224
+    }
+    CCMode {
+        kCCModeECB, kCCModeCBC, kCCModeCFB, kCCModeCTR, kCCModeOFB, kCCModeXTS, kCCModeRC4,
+        kCCModeCFB8, kCCModeGCM, kCCModeCCM
+    }
+    CCOperation { kCCEncrypt, kCCDecrypt }
+    CCPadding { ccNoPadding, ccPKCS7Padding }
+    CCPBKDFAlgorithm { kCCPBKDF2 }
+    CCPseudoRandomAlgorithm {
+        kCCPRFHmacAlgSHA1, kCCPRFHmacAlgSHA224, kCCPRFHmacAlgSHA256, kCCPRFHmacAlgSHA384,
+        kCCPRFHmacAlgSHA512
+    }
+}
+
+/// Converts the raw value of a C enum argument, failing with `kCCParamError` if it is out of
+/// range.
+fn param<T: FromRaw>(value: c_uint) -> Status<T> {
+    T::from_raw(value).ok_or(CCCryptorStatus::kCCParamError)
+}
+
+/// Converts the result of an operation into a `CommonCrypto` status code.
+fn status(result: Status<()>) -> c_int {
+    match result {
+        Ok(()) => CCCryptorStatus::kCCSuccess as c_int,
+        Err(status) => status as c_int,
+    }
+}
+
+/// Borrows the `len` bytes at `ptr`, which may be null or dangling if `len` is zero.
+unsafe fn input<'a>(ptr: *const u8, len: usize) -> Status<&'a [u8]> {
+    if len == 0 {
+        Ok(&[])
+    } else if ptr.is_null() {
+        Err(CCCryptorStatus::kCCParamError)
+    } else {
+        Ok(slice::from_raw_parts(ptr, len))
+    }
+}
+
+/// Mutably borrows the `len` bytes at `ptr`, which may be null or dangling if `len` is zero.
+unsafe fn output<'a>(ptr: *mut u8, len: usize) -> Status<&'a mut [u8]> {
+    if len == 0 {
+        Ok(&mut [])
+    } else if ptr.is_null() {
+        Err(CCCryptorStatus::kCCParamError)
+    } else {
+        Ok(slice::from_raw_parts_mut(ptr, len))
+    }
+}
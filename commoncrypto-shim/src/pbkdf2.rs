@@ -0,0 +1,141 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CCKeyDerivationPBKDF` function.
+
+use commoncrypto_sys::{
+    CCCryptorStatus, CCHmacAlgorithm, CCPBKDFAlgorithm, CCPseudoRandomAlgorithm,
+};
+use hmac::HmacState;
+use std::os::raw::{c_int, c_uint};
+use std::time::{Duration, Instant};
+use {input, output, param, status, FromRaw, Status};
+
+/// Minimum duration of the derivation which is timed to calibrate the number of rounds.
+const CALIBRATION_SAMPLE: Duration = Duration::from_millis(10);
+
+fn hmac_algorithm(prf: CCPseudoRandomAlgorithm) -> CCHmacAlgorithm {
+    match prf {
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1 => CCHmacAlgorithm::kCCHmacAlgSHA1,
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA224 => CCHmacAlgorithm::kCCHmacAlgSHA224,
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256 => CCHmacAlgorithm::kCCHmacAlgSHA256,
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA384 => CCHmacAlgorithm::kCCHmacAlgSHA384,
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA512 => CCHmacAlgorithm::kCCHmacAlgSHA512,
+    }
+}
+
+/// PBKDF2 as specified in RFC 8018, section 5.2.
+fn derive(
+    password: &[u8],
+    salt: &[u8],
+    prf: CCPseudoRandomAlgorithm,
+    rounds: u32,
+    derived_key: &mut [u8],
+) -> Status<()> {
+    if rounds == 0 || derived_key.is_empty() {
+        return Err(CCCryptorStatus::kCCParamError);
+    }
+    let keyed = HmacState::new(hmac_algorithm(prf), password);
+    let mut u = vec![0u8; keyed.output_size()];
+    for (i, block) in derived_key.chunks_mut(u.len()).enumerate() {
+        let mut mac = keyed;
+        mac.update(salt);
+        mac.update(&(i as u32 + 1).to_be_bytes());
+        mac.finish(&mut u);
+        let mut t = u.clone();
+        for _ in 1..rounds {
+            let mut mac = keyed;
+            mac.update(&u);
+            mac.finish(&mut u);
+            for (t, u) in t.iter_mut().zip(u.iter()) {
+                *t ^= u;
+            }
+        }
+        block.copy_from_slice(&t[..block.len()]);
+    }
+    Ok(())
+}
+
+/// Derive a key from a user-supplied password via PBKDF2.
+#[allow(clippy::too_many_arguments)]
+#[no_mangle]
+pub unsafe extern "C" fn CCKeyDerivationPBKDF(
+    algorithm: c_uint,
+    password: *const u8,
+    passwordLen: usize,
+    salt: *const u8,
+    saltLen: usize,
+    prf: c_uint,
+    rounds: u32,
+    derivedKey: *mut u8,
+    derivedKeyLen: usize,
+) -> c_int {
+    fail_point!("CCKeyDerivationPBKDF");
+    status(input(password, passwordLen).and_then(|password| {
+        let salt = input(salt, saltLen)?;
+        let derived_key = output(derivedKey, derivedKeyLen)?;
+        match param(algorithm)? {
+            CCPBKDFAlgorithm::kCCPBKDF2 => derive(password, salt, param(prf)?, rounds, derived_key),
+        }
+    }))
+}
+
+/// Determine the number of PRF rounds to use for a specific delay on the current platform.
+/// Returns `u32::MAX` (`-1`) on failure.
+///
+/// Derivations with the given lengths are timed, with the number of rounds doubling until they
+/// take long enough to be measured, and the rounds are scaled to the requested delay.
+#[no_mangle]
+pub extern "C" fn CCCalibratePBKDF(
+    algorithm: c_uint,
+    passwordLen: usize,
+    saltLen: usize,
+    prf: c_uint,
+    derivedKeyLen: usize,
+    msec: u32,
+) -> c_uint {
+    if ::failure::injected("CCCalibratePBKDF").is_some() || derivedKeyLen == 0 {
+        return c_uint::MAX;
+    }
+    let prf = match (
+        CCPBKDFAlgorithm::from_raw(algorithm),
+        CCPseudoRandomAlgorithm::from_raw(prf),
+    ) {
+        (Some(CCPBKDFAlgorithm::kCCPBKDF2), Some(prf)) => prf,
+        _ => return c_uint::MAX,
+    };
+    let password = vec![0u8; passwordLen];
+    let salt = vec![0u8; saltLen];
+    let mut derived_key = vec![0u8; derivedKeyLen];
+    let mut rounds = 1000u32;
+    loop {
+        let start = Instant::now();
+        if derive(&password, &salt, prf, rounds, &mut derived_key).is_err() {
+            return c_uint::MAX;
+        }
+        let elapsed = start.elapsed();
+        if elapsed >= CALIBRATION_SAMPLE || rounds > c_uint::MAX / 2 {
+            let per_round = elapsed.as_secs_f64() / f64::from(rounds);
+            let calibrated = f64::from(msec) / 1000.0 / per_round;
+            return calibrated.max(1.0).min(f64::from(c_uint::MAX - 1)) as c_uint;
+        }
+        rounds *= 2;
+    }
+}
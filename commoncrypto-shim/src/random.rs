@@ -0,0 +1,70 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Software implementation of the `CCRandom*` functions, backed by the operating system's random
+//! number generator.
+
+use commoncrypto_sys::{CCCryptorStatus, CCRandom, CCRandomRef};
+use getrandom::getrandom;
+use std::os::raw::c_int;
+use {output, status};
+
+/// Reference to a random number generator, which can be shared between threads.
+#[repr(transparent)]
+pub struct RandomRef(CCRandomRef);
+
+unsafe impl Sync for RandomRef {}
+
+static DEFAULT: u8 = 0;
+
+/// The default random number generator, for use with `CCRandomCopyBytes()`.
+#[no_mangle]
+#[allow(non_upper_case_globals)]
+pub static kCCRandomDefault: RandomRef = RandomRef(&DEFAULT as *const u8 as *const CCRandom);
+
+unsafe fn generate(bytes: *mut u8, count: usize) -> c_int {
+    status(
+        output(bytes, count)
+            .and_then(|bytes| getrandom(bytes).map_err(|_| CCCryptorStatus::kCCRNGFailure)),
+    )
+}
+
+/// Fill `bytes` with random data from the given generator. Returns `0` on success.
+///
+/// Every generator is backed by the operating system's random number generator.
+#[no_mangle]
+pub unsafe extern "C" fn CCRandomCopyBytes(
+    rnd: CCRandomRef,
+    bytes: *mut u8,
+    count: usize,
+) -> c_int {
+    fail_point!("CCRandomCopyBytes");
+    if rnd.is_null() {
+        return status(Err(CCCryptorStatus::kCCParamError));
+    }
+    generate(bytes, count)
+}
+
+/// Fill `bytes` with cryptographically strong random data. Returns `0` on success.
+#[no_mangle]
+pub unsafe extern "C" fn CCRandomGenerateBytes(bytes: *mut u8, count: usize) -> c_int {
+    fail_point!("CCRandomGenerateBytes");
+    generate(bytes, count)
+}
@@ -0,0 +1,88 @@
+extern crate commoncrypto_shim;
+
+use std::os::raw::{c_int, c_uint, c_void};
+use std::ptr;
+
+// Declared with raw enum values, as a C caller sees them
+extern "C" {
+    fn CCDigest(algorithm: c_uint, data: *const u8, length: usize, out: *mut u8) -> c_int;
+    fn CCDigestCreate(algorithm: c_uint) -> *mut c_void;
+    fn CCDigestGetOutputSize(algorithm: c_uint) -> usize;
+    fn CCHmacInit(ctx: *mut c_void, algorithm: c_uint, key: *const u8, keyLength: usize);
+    fn CCKeyDerivationPBKDF(
+        algorithm: c_uint,
+        password: *const u8,
+        passwordLen: usize,
+        salt: *const u8,
+        saltLen: usize,
+        prf: c_uint,
+        rounds: u32,
+        derivedKey: *mut u8,
+        derivedKeyLen: usize,
+    ) -> c_int;
+    fn CCCryptorCreate(
+        op: c_uint,
+        alg: c_uint,
+        options: u32,
+        key: *const u8,
+        keyLength: usize,
+        iv: *const u8,
+        cryptorRef: *mut *mut c_void,
+    ) -> c_int;
+}
+
+const PARAM_ERROR: c_int = -4300;
+const OUT_OF_RANGE: c_uint = 0xdead;
+
+#[test]
+fn out_of_range_enums() {
+    let key = [0u8; 16];
+    let mut out = [0u8; 64];
+    let mut ctx = [0u8; 512];
+    let mut cryptor = ptr::null_mut();
+    unsafe {
+        assert_eq!(
+            CCDigest(OUT_OF_RANGE, ptr::null(), 0, out.as_mut_ptr()),
+            PARAM_ERROR
+        );
+        assert!(CCDigestCreate(OUT_OF_RANGE).is_null());
+        assert_eq!(CCDigestGetOutputSize(OUT_OF_RANGE), 0);
+
+        // An HMAC context which was not initialized stays zeroed
+        CCHmacInit(
+            ctx.as_mut_ptr() as *mut c_void,
+            OUT_OF_RANGE,
+            key.as_ptr(),
+            key.len(),
+        );
+        assert!(ctx.iter().all(|byte| *byte == 0));
+
+        assert_eq!(
+            CCKeyDerivationPBKDF(
+                2,
+                key.as_ptr(),
+                key.len(),
+                key.as_ptr(),
+                key.len(),
+                OUT_OF_RANGE,
+                1,
+                out.as_mut_ptr(),
+                32,
+            ),
+            PARAM_ERROR
+        );
+        assert_eq!(
+            CCCryptorCreate(
+                0,
+                OUT_OF_RANGE,
+                0,
+                key.as_ptr(),
+                key.len(),
+                ptr::null(),
+                &mut cryptor,
+            ),
+            PARAM_ERROR
+        );
+        assert!(cryptor.is_null());
+    }
+}
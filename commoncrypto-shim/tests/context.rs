@@ -0,0 +1,107 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+extern crate commoncrypto_shim;
+extern crate commoncrypto_sys;
+extern crate hex;
+
+use commoncrypto_sys::{CCHmacAlgorithm, CCHmacContext, CC_LONG, CC_SHA256_CTX, CC_SHA512_CTX};
+
+// Hashes of 1000 "a"s, which span several blocks and leave a partial one
+const DATA: [u8; 1000] = [b'a'; 1000];
+const SHA256_HASH: &str = "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3";
+const SHA384_HASH: &str = "f54480689c6b0b11d0303285d9a81b21a93bca6ba5a1b4472765dca4da45ee328082d469c650cd3b61b16d3266ab8ced";
+// HMAC-SHA384 of the same data, with a key longer than the block size
+const HMAC_KEY: [u8; 200] = [b'k'; 200];
+const HMAC_SHA384: &str = "83000004b36082ac7ecd10d5132e06a6f7b7f8cfbd4d3dd1332c64a398c4e8bd9dad570c2ae998d87355822c06789fe5";
+
+#[test]
+fn copied_hash_context_continues_independently() {
+    let mut ctx = CC_SHA256_CTX::default();
+    let mut copy_hash = [0u8; commoncrypto_sys::SHA256_DIGEST_LENGTH];
+    let mut hash = [0u8; commoncrypto_sys::SHA256_DIGEST_LENGTH];
+    unsafe {
+        assert_eq!(1, commoncrypto_sys::CC_SHA256_Init(&mut ctx));
+        assert_eq!(
+            1,
+            commoncrypto_sys::CC_SHA256_Update(&mut ctx, DATA.as_ptr(), 500)
+        );
+        let mut copy = ctx.clone();
+        assert_eq!(
+            1,
+            commoncrypto_sys::CC_SHA256_Update(&mut copy, DATA[500..].as_ptr(), 500)
+        );
+        assert_eq!(
+            1,
+            commoncrypto_sys::CC_SHA256_Final(copy_hash.as_mut_ptr(), &mut copy)
+        );
+        assert_eq!(
+            1,
+            commoncrypto_sys::CC_SHA256_Update(&mut ctx, DATA.as_ptr(), 500)
+        );
+        assert_eq!(
+            1,
+            commoncrypto_sys::CC_SHA256_Final(hash.as_mut_ptr(), &mut ctx)
+        );
+    }
+    assert_eq!(hex::encode(copy_hash), SHA256_HASH);
+    assert_eq!(hex::encode(hash), SHA256_HASH);
+}
+
+#[test]
+fn sha384_context() {
+    let mut ctx = CC_SHA512_CTX::default();
+    let mut hash = [0u8; commoncrypto_sys::SHA384_DIGEST_LENGTH];
+    unsafe {
+        assert_eq!(1, commoncrypto_sys::CC_SHA384_Init(&mut ctx));
+        for chunk in DATA.chunks(7) {
+            commoncrypto_sys::CC_SHA384_Update(&mut ctx, chunk.as_ptr(), chunk.len() as CC_LONG);
+        }
+        assert_eq!(
+            1,
+            commoncrypto_sys::CC_SHA384_Final(hash.as_mut_ptr(), &mut ctx)
+        );
+    }
+    assert_eq!(hex::encode(&hash[..]), SHA384_HASH);
+}
+
+#[test]
+fn copied_hmac_context_continues_independently() {
+    let mut ctx = CCHmacContext::default();
+    let mut copy_mac = [0u8; commoncrypto_sys::SHA384_DIGEST_LENGTH];
+    let mut mac = [0u8; commoncrypto_sys::SHA384_DIGEST_LENGTH];
+    unsafe {
+        commoncrypto_sys::CCHmacInit(
+            &mut ctx,
+            CCHmacAlgorithm::kCCHmacAlgSHA384,
+            HMAC_KEY.as_ptr(),
+            HMAC_KEY.len(),
+        );
+        commoncrypto_sys::CCHmacUpdate(&mut ctx, DATA.as_ptr(), 300);
+        let mut copy = ctx.clone();
+        commoncrypto_sys::CCHmacUpdate(&mut copy, DATA[300..].as_ptr(), 700);
+        commoncrypto_sys::CCHmacFinal(&mut copy, copy_mac.as_mut_ptr());
+        commoncrypto_sys::CCHmacUpdate(&mut ctx, DATA.as_ptr(), 700);
+        commoncrypto_sys::CCHmacFinal(&mut ctx, mac.as_mut_ptr());
+    }
+    assert_eq!(hex::encode(&copy_mac[..]), HMAC_SHA384);
+    assert_eq!(hex::encode(&mac[..]), HMAC_SHA384);
+    assert_eq!(ctx, CCHmacContext::default());
+}
@@ -0,0 +1,77 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+extern crate commoncrypto_shim;
+extern crate commoncrypto_sys;
+
+use commoncrypto_shim::{clear_failures, inject_failure};
+use commoncrypto_sys::{CCCryptorStatus, CCDigestAlgorithm};
+use std::os::raw::c_int;
+use std::thread;
+
+#[test]
+fn injected_status() {
+    let mut bytes = [0u8; 16];
+    inject_failure(
+        "CCRandomGenerateBytes",
+        CCCryptorStatus::kCCRNGFailure as c_int,
+    );
+    let status = unsafe { commoncrypto_sys::CCRandomGenerateBytes(bytes.as_mut_ptr(), 16) };
+    assert_eq!(status, CCCryptorStatus::kCCRNGFailure as c_int);
+
+    clear_failures();
+    let status = unsafe { commoncrypto_sys::CCRandomGenerateBytes(bytes.as_mut_ptr(), 16) };
+    assert_eq!(status, CCCryptorStatus::kCCSuccess as c_int);
+}
+
+#[test]
+fn injected_null_pointer() {
+    inject_failure("CCDigestCreate", CCCryptorStatus::kCCMemoryFailure as c_int);
+    let ctx = unsafe { commoncrypto_sys::CCDigestCreate(CCDigestAlgorithm::kCCDigestSHA256) };
+    assert!(ctx.is_null());
+    clear_failures();
+}
+
+#[test]
+fn injected_oneshot_failure() {
+    let mut md = [0u8; 32];
+    inject_failure("CC_SHA256_Update", 0);
+    let result = unsafe { commoncrypto_sys::CC_SHA256(b"abc".as_ptr(), 3, md.as_mut_ptr()) };
+    assert!(result.is_null());
+    clear_failures();
+    let result = unsafe { commoncrypto_sys::CC_SHA256(b"abc".as_ptr(), 3, md.as_mut_ptr()) };
+    assert_eq!(result, md.as_mut_ptr());
+}
+
+#[test]
+fn injected_failures_are_per_thread() {
+    let mut bytes = [0u8; 16];
+    inject_failure(
+        "CCRandomGenerateBytes",
+        CCCryptorStatus::kCCRNGFailure as c_int,
+    );
+    let status = thread::spawn(move || unsafe {
+        commoncrypto_sys::CCRandomGenerateBytes(bytes.as_mut_ptr(), 16)
+    })
+    .join()
+    .unwrap();
+    assert_eq!(status, CCCryptorStatus::kCCSuccess as c_int);
+    clear_failures();
+}
@@ -22,7 +22,28 @@
 
 #![warn(missing_docs)]
 
+use std::error;
+use std::fmt;
 use std::os::raw::{c_int, c_uint};
+use std::str::FromStr;
+
+/// Length of the data passed to the `CC_*` hash functions.
+#[allow(non_camel_case_types)]
+pub type CC_LONG = u32;
+
+/// Number of 32-bit words in an MD2 block, as `CommonCrypto` counts it.
+const MD2_BLOCK_LONG: usize = MD2_BLOCK_BYTES / 4;
+/// Number of bytes for an MD2 hash.
+pub const MD2_DIGEST_LENGTH: usize = 16;
+/// Number of bytes in an MD2 block, as `CommonCrypto` counts it.
+pub const MD2_BLOCK_BYTES: usize = 64;
+
+/// Number of 32-bit words in an MD4 block.
+const MD4_BLOCK_LONG: usize = MD4_BLOCK_BYTES / 4;
+/// Number of bytes for an MD4 hash.
+pub const MD4_DIGEST_LENGTH: usize = 16;
+/// Number of bytes in an MD4 block.
+pub const MD4_BLOCK_BYTES: usize = 64;
 
 /// Total number of operations.
 const MD5_CBLOCK: usize = 64;
@@ -30,30 +51,90 @@ const MD5_CBLOCK: usize = 64;
 const MD5_LBLOCK: usize = MD5_CBLOCK / 4;
 /// Number of bytes for an MD5 hash.
 pub const MD5_DIGEST_LENGTH: usize = 16;
+/// Number of bytes in an MD5 block.
+pub const MD5_BLOCK_BYTES: usize = MD5_CBLOCK;
 
 const SHA_LBLOCK: usize = 16;
 /// Number of bytes for an SHA1 hash.
 pub const SHA1_DIGEST_LENGTH: usize = 20;
+/// Number of bytes in an SHA1 block.
+pub const SHA1_BLOCK_BYTES: usize = 64;
+/// Number of bytes for an SHA224 hash.
+pub const SHA224_DIGEST_LENGTH: usize = 28;
+/// Number of bytes in an SHA224 block.
+pub const SHA224_BLOCK_BYTES: usize = 64;
 /// Number of bytes for an SHA256 hash.
 pub const SHA256_DIGEST_LENGTH: usize = 32;
+/// Number of bytes in an SHA256 block.
+pub const SHA256_BLOCK_BYTES: usize = 64;
 /// Number of bytes for an SHA384 hash.
 pub const SHA384_DIGEST_LENGTH: usize = 48;
+/// Number of bytes in an SHA384 block.
+pub const SHA384_BLOCK_BYTES: usize = 128;
 /// Number of bytes for an SHA512 hash.
 pub const SHA512_DIGEST_LENGTH: usize = 64;
+/// Number of bytes in an SHA512 block.
+pub const SHA512_BLOCK_BYTES: usize = 128;
+
+/// Struct used to generate MD2 hashes.
+#[allow(non_camel_case_types, non_snake_case)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct CC_MD2_CTX {
+    /// Number of bytes in `data`
+    pub num: c_int,
+    /// Partial block
+    pub data: [u8; MD2_DIGEST_LENGTH],
+    /// Checksum, one byte per word
+    pub cksm: [c_uint; MD2_BLOCK_LONG],
+    /// State, one byte per word
+    pub state: [c_uint; MD2_BLOCK_LONG],
+}
+
+/// Struct used to generate MD4 hashes.
+#[allow(non_camel_case_types, non_snake_case)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct CC_MD4_CTX {
+    /// Chaining word
+    pub A: c_uint,
+    /// Chaining word
+    pub B: c_uint,
+    /// Chaining word
+    pub C: c_uint,
+    /// Chaining word
+    pub D: c_uint,
+    /// Low word of the number of bits hashed
+    pub Nl: c_uint,
+    /// High word of the number of bits hashed
+    pub Nh: c_uint,
+    /// Partial block, as bytes
+    pub data: [c_uint; MD4_BLOCK_LONG],
+    /// Number of bytes in `data`
+    pub num: c_uint,
+}
 
 /// Struct used to generate MD5 hashes.
 #[allow(non_camel_case_types, non_snake_case)]
 #[derive(Clone, Debug, Default, PartialEq)]
 #[repr(C)]
 pub struct CC_MD5_CTX {
-    A: c_uint,
-    B: c_uint,
-    C: c_uint,
-    D: c_uint,
-    Nl: c_uint,
-    Nh: c_uint,
-    data: [c_uint; MD5_LBLOCK],
-    num: c_uint,
+    /// Chaining word
+    pub A: c_uint,
+    /// Chaining word
+    pub B: c_uint,
+    /// Chaining word
+    pub C: c_uint,
+    /// Chaining word
+    pub D: c_uint,
+    /// Low word of the number of bits hashed
+    pub Nl: c_uint,
+    /// High word of the number of bits hashed
+    pub Nh: c_uint,
+    /// Partial block, as bytes
+    pub data: [c_uint; MD5_LBLOCK],
+    /// Number of bytes in `data`
+    pub num: c_uint,
 }
 
 /// Struct used to generate SHA1 hashes.
@@ -61,15 +142,24 @@ pub struct CC_MD5_CTX {
 #[derive(Clone, Debug, Default, PartialEq)]
 #[repr(C)]
 pub struct CC_SHA_CTX {
-    h0: c_uint,
-    h1: c_uint,
-    h2: c_uint,
-    h3: c_uint,
-    h4: c_uint,
-    Nl: c_uint,
-    Nh: c_uint,
-    data: [c_uint; SHA_LBLOCK],
-    num: c_uint,
+    /// Chaining word
+    pub h0: c_uint,
+    /// Chaining word
+    pub h1: c_uint,
+    /// Chaining word
+    pub h2: c_uint,
+    /// Chaining word
+    pub h3: c_uint,
+    /// Chaining word
+    pub h4: c_uint,
+    /// Low word of the number of bits hashed
+    pub Nl: c_uint,
+    /// High word of the number of bits hashed
+    pub Nh: c_uint,
+    /// Partial block, as bytes
+    pub data: [c_uint; SHA_LBLOCK],
+    /// Number of bytes in `data`
+    pub num: c_uint,
 }
 
 macro_rules! cc_sha2_struct {
@@ -79,9 +169,12 @@ macro_rules! cc_sha2_struct {
         #[derive(Clone, Debug, Default, PartialEq)]
         #[repr(C)]
         pub struct $ctx_name {
-            count: [$ty; 2],
-            hash: [$ty; 8],
-            wbuf: [$ty; 16],
+            /// Number of bytes hashed, low word first
+            pub count: [$ty; 2],
+            /// Chaining words
+            pub hash: [$ty; 8],
+            /// Partial block, as bytes
+            pub wbuf: [$ty; 16],
         }
     };
 }
@@ -89,8 +182,64 @@ macro_rules! cc_sha2_struct {
 cc_sha2_struct!(CC_SHA256_CTX, u32);
 cc_sha2_struct!(CC_SHA512_CTX, u64);
 
+/// Status codes returned by `CommonCrypto` functions.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub enum CCCryptorStatus {
+    /// Operation completed normally
+    kCCSuccess = 0,
+    /// Illegal parameter value
+    kCCParamError = -4300,
+    /// Insufficent buffer provided for specified operation
+    kCCBufferTooSmall = -4301,
+    /// Memory allocation failure
+    kCCMemoryFailure = -4302,
+    /// Input size was not aligned properly
+    kCCAlignmentError = -4303,
+    /// Input data did not decode or decrypt properly
+    kCCDecodeError = -4304,
+    /// Function not implemented for the current algorithm
+    kCCUnimplemented = -4305,
+    /// Operation would overflow
+    kCCOverflow = -4306,
+    /// Random number generator failure
+    kCCRNGFailure = -4307,
+    /// Unspecified error
+    kCCUnspecifiedError = -4308,
+    /// Functions called in the wrong order
+    kCCCallSequenceError = -4309,
+    /// Invalid key size for the algorithm
+    kCCKeySizeError = -4310,
+    /// Key is not valid
+    kCCInvalidKey = -4311,
+}
+
+impl CCCryptorStatus {
+    /// Converts a status code returned by a `CommonCrypto` function, if it is documented.
+    pub fn from_raw(status: c_int) -> Option<CCCryptorStatus> {
+        match status {
+            0 => Some(CCCryptorStatus::kCCSuccess),
+            -4300 => Some(CCCryptorStatus::kCCParamError),
+            -4301 => Some(CCCryptorStatus::kCCBufferTooSmall),
+            -4302 => Some(CCCryptorStatus::kCCMemoryFailure),
+            -4303 => Some(CCCryptorStatus::kCCAlignmentError),
+            -4304 => Some(CCCryptorStatus::kCCDecodeError),
+            -4305 => Some(CCCryptorStatus::kCCUnimplemented),
+            -4306 => Some(CCCryptorStatus::kCCOverflow),
+            -4307 => Some(CCCryptorStatus::kCCRNGFailure),
+            -4308 => Some(CCCryptorStatus::kCCUnspecifiedError),
+            -4309 => Some(CCCryptorStatus::kCCCallSequenceError),
+            -4310 => Some(CCCryptorStatus::kCCKeySizeError),
+            -4311 => Some(CCCryptorStatus::kCCInvalidKey),
+            _ => None,
+        }
+    }
+}
+
 /// Digest algorithm used in `CCDigest*()` functions.
 #[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub enum CCDigestAlgorithm {
     /// No digest algorithm
@@ -133,6 +282,160 @@ pub enum CCDigestAlgorithm {
     kCCDigestSkein512 = 19,
 }
 
+impl CCDigestAlgorithm {
+    /// Number of bytes in the digest output of the algorithm.
+    pub const fn output_len(&self) -> usize {
+        match *self {
+            CCDigestAlgorithm::kCCDigestNone => 0,
+            CCDigestAlgorithm::kCCDigestMD2
+            | CCDigestAlgorithm::kCCDigestMD4
+            | CCDigestAlgorithm::kCCDigestMD5
+            | CCDigestAlgorithm::kCCDigestRMD128
+            | CCDigestAlgorithm::kCCDigestSkein128 => MD5_DIGEST_LENGTH,
+            CCDigestAlgorithm::kCCDigestRMD160
+            | CCDigestAlgorithm::kCCDigestSHA1
+            | CCDigestAlgorithm::kCCDigestSkein160 => SHA1_DIGEST_LENGTH,
+            CCDigestAlgorithm::kCCDigestSHA224 | CCDigestAlgorithm::kCCDigestSkein224 => {
+                SHA224_DIGEST_LENGTH
+            }
+            CCDigestAlgorithm::kCCDigestRMD256
+            | CCDigestAlgorithm::kCCDigestSHA256
+            | CCDigestAlgorithm::kCCDigestSkein256 => SHA256_DIGEST_LENGTH,
+            CCDigestAlgorithm::kCCDigestRMD320 => 40,
+            CCDigestAlgorithm::kCCDigestSHA384 | CCDigestAlgorithm::kCCDigestSkein384 => {
+                SHA384_DIGEST_LENGTH
+            }
+            CCDigestAlgorithm::kCCDigestSHA512 | CCDigestAlgorithm::kCCDigestSkein512 => {
+                SHA512_DIGEST_LENGTH
+            }
+        }
+    }
+
+    /// DER encoding of the ASN.1 object identifier of the algorithm, including its tag and length,
+    /// as used by `CCDigestCreateByOID`. Not every algorithm has one.
+    pub const fn oid(&self) -> Option<&'static [u8]> {
+        match *self {
+            CCDigestAlgorithm::kCCDigestMD2 => Some(b"\x06\x08\x2a\x86\x48\x86\xf7\x0d\x02\x02"),
+            CCDigestAlgorithm::kCCDigestMD4 => Some(b"\x06\x08\x2a\x86\x48\x86\xf7\x0d\x02\x04"),
+            CCDigestAlgorithm::kCCDigestMD5 => Some(b"\x06\x08\x2a\x86\x48\x86\xf7\x0d\x02\x05"),
+            CCDigestAlgorithm::kCCDigestRMD128 => Some(b"\x06\x05\x2b\x24\x03\x02\x02"),
+            CCDigestAlgorithm::kCCDigestRMD160 => Some(b"\x06\x05\x2b\x24\x03\x02\x01"),
+            CCDigestAlgorithm::kCCDigestRMD256 => Some(b"\x06\x05\x2b\x24\x03\x02\x03"),
+            CCDigestAlgorithm::kCCDigestSHA1 => Some(b"\x06\x05\x2b\x0e\x03\x02\x1a"),
+            CCDigestAlgorithm::kCCDigestSHA224 => {
+                Some(b"\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x04")
+            }
+            CCDigestAlgorithm::kCCDigestSHA256 => {
+                Some(b"\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x01")
+            }
+            CCDigestAlgorithm::kCCDigestSHA384 => {
+                Some(b"\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x02")
+            }
+            CCDigestAlgorithm::kCCDigestSHA512 => {
+                Some(b"\x06\x09\x60\x86\x48\x01\x65\x03\x04\x02\x03")
+            }
+            _ => None,
+        }
+    }
+
+    /// Finds the algorithm with the given DER-encoded object identifier. See `oid`.
+    pub fn from_oid(oid: &[u8]) -> Option<CCDigestAlgorithm> {
+        CCDigestAlgorithm::all().find(|algorithm| algorithm.oid() == Some(oid))
+    }
+
+    /// Every digest algorithm except `kCCDigestNone`, including those which the current host
+    /// may not support.
+    pub const ALL: [CCDigestAlgorithm; 18] = [
+        CCDigestAlgorithm::kCCDigestMD2,
+        CCDigestAlgorithm::kCCDigestMD4,
+        CCDigestAlgorithm::kCCDigestMD5,
+        CCDigestAlgorithm::kCCDigestRMD128,
+        CCDigestAlgorithm::kCCDigestRMD160,
+        CCDigestAlgorithm::kCCDigestRMD256,
+        CCDigestAlgorithm::kCCDigestRMD320,
+        CCDigestAlgorithm::kCCDigestSHA1,
+        CCDigestAlgorithm::kCCDigestSHA224,
+        CCDigestAlgorithm::kCCDigestSHA256,
+        CCDigestAlgorithm::kCCDigestSHA384,
+        CCDigestAlgorithm::kCCDigestSHA512,
+        CCDigestAlgorithm::kCCDigestSkein128,
+        CCDigestAlgorithm::kCCDigestSkein160,
+        CCDigestAlgorithm::kCCDigestSkein224,
+        CCDigestAlgorithm::kCCDigestSkein256,
+        CCDigestAlgorithm::kCCDigestSkein384,
+        CCDigestAlgorithm::kCCDigestSkein512,
+    ];
+
+    /// Iterates over `ALL`.
+    pub fn all() -> impl Iterator<Item = CCDigestAlgorithm> {
+        CCDigestAlgorithm::ALL.iter().cloned()
+    }
+
+    /// Conventional name of the algorithm, such as `SHA-256`. `FromStr` parses it.
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            CCDigestAlgorithm::kCCDigestNone => "none",
+            CCDigestAlgorithm::kCCDigestMD2 => "MD2",
+            CCDigestAlgorithm::kCCDigestMD4 => "MD4",
+            CCDigestAlgorithm::kCCDigestMD5 => "MD5",
+            CCDigestAlgorithm::kCCDigestRMD128 => "RIPEMD-128",
+            CCDigestAlgorithm::kCCDigestRMD160 => "RIPEMD-160",
+            CCDigestAlgorithm::kCCDigestRMD256 => "RIPEMD-256",
+            CCDigestAlgorithm::kCCDigestRMD320 => "RIPEMD-320",
+            CCDigestAlgorithm::kCCDigestSHA1 => "SHA-1",
+            CCDigestAlgorithm::kCCDigestSHA224 => "SHA-224",
+            CCDigestAlgorithm::kCCDigestSHA256 => "SHA-256",
+            CCDigestAlgorithm::kCCDigestSHA384 => "SHA-384",
+            CCDigestAlgorithm::kCCDigestSHA512 => "SHA-512",
+            CCDigestAlgorithm::kCCDigestSkein128 => "Skein-128",
+            CCDigestAlgorithm::kCCDigestSkein160 => "Skein-160",
+            CCDigestAlgorithm::kCCDigestSkein224 => "Skein-224",
+            CCDigestAlgorithm::kCCDigestSkein256 => "Skein-256",
+            CCDigestAlgorithm::kCCDigestSkein384 => "Skein-384",
+            CCDigestAlgorithm::kCCDigestSkein512 => "Skein-512",
+        }
+    }
+}
+
+impl fmt::Display for CCDigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned when a digest algorithm name is not recognized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDigestAlgorithmError;
+
+impl fmt::Display for ParseDigestAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown digest algorithm")
+    }
+}
+
+impl error::Error for ParseDigestAlgorithmError {}
+
+impl FromStr for CCDigestAlgorithm {
+    type Err = ParseDigestAlgorithmError;
+
+    /// Parses the name of an algorithm, such as `sha256`, `SHA-512` or `md5`. Case, hyphens and
+    /// underscores are ignored, and `RMD` is accepted for `RIPEMD`. `none` is not accepted.
+    fn from_str(name: &str) -> Result<CCDigestAlgorithm, ParseDigestAlgorithmError> {
+        let name = normalize_algorithm_name(name);
+        CCDigestAlgorithm::all()
+            .find(|algorithm| normalize_algorithm_name(algorithm.name()) == name)
+            .ok_or(ParseDigestAlgorithmError)
+    }
+}
+
+fn normalize_algorithm_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<String>()
+        .replace("ripemd", "rmd")
+}
+
 const CC_DIGEST_SIZE: usize = 1032;
 
 /// Context used in `CCDigest*()` functions.
@@ -144,6 +447,7 @@ pub struct CCDigestCtx {
 
 /// Algorithm used in `CCKeyDerivationPBKDF()`.
 #[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub enum CCPBKDFAlgorithm {
     /// PBKDF2
@@ -152,6 +456,7 @@ pub enum CCPBKDFAlgorithm {
 
 /// Pseudo-random algorithm used in `CCKeyDerivationPBKDF()`.
 #[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub enum CCPseudoRandomAlgorithm {
     /// SHA-1
@@ -166,37 +471,256 @@ pub enum CCPseudoRandomAlgorithm {
     kCCPRFHmacAlgSHA512 = 5,
 }
 
+/// Opaque random number generator used in `CCRandomCopyBytes()`.
+#[repr(C)]
+pub struct CCRandom {
+    _private: [u8; 0],
+}
+
+/// Reference to a `CCRandom`.
+pub type CCRandomRef = *const CCRandom;
+
+/// Algorithm used in `CCHmac*()` functions.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub enum CCHmacAlgorithm {
+    /// HMAC with SHA1 digest
+    kCCHmacAlgSHA1 = 0,
+    /// HMAC with MD5 digest
+    kCCHmacAlgMD5 = 1,
+    /// HMAC with SHA256 digest
+    kCCHmacAlgSHA256 = 2,
+    /// HMAC with SHA384 digest
+    kCCHmacAlgSHA384 = 3,
+    /// HMAC with SHA512 digest
+    kCCHmacAlgSHA512 = 4,
+    /// HMAC with SHA224 digest
+    kCCHmacAlgSHA224 = 5,
+}
+
+const CC_HMAC_CONTEXT_SIZE: usize = 96;
+
+/// Context used in `CCHmac*()` functions.
+#[allow(non_camel_case_types, non_snake_case)]
+#[derive(Clone, Debug, PartialEq)]
+#[repr(C)]
+pub struct CCHmacContext {
+    ctx: [u32; CC_HMAC_CONTEXT_SIZE],
+}
+
+impl Default for CCHmacContext {
+    fn default() -> Self {
+        CCHmacContext {
+            ctx: [0; CC_HMAC_CONTEXT_SIZE],
+        }
+    }
+}
+
+/// Operation performed by a `CCCryptor`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub enum CCOperation {
+    /// Symmetric encryption
+    kCCEncrypt = 0,
+    /// Symmetric decryption
+    kCCDecrypt = 1,
+}
+
+/// Encryption algorithm used by a `CCCryptor`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub enum CCAlgorithm {
+    /// Advanced Encryption Standard, 128-bit block (also known as `kCCAlgorithmAES128`)
+    kCCAlgorithmAES = 0,
+    /// Data Encryption Standard
+    kCCAlgorithmDES = 1,
+    /// Triple-DES, three key, EDE configuration
+    kCCAlgorithm3DES = 2,
+    /// CAST
+    kCCAlgorithmCAST = 3,
+    /// RC4 stream cipher
+    kCCAlgorithmRC4 = 4,
+    /// RC2
+    kCCAlgorithmRC2 = 5,
+    /// Blowfish
+    kCCAlgorithmBlowfish = 6,
+}
+
+/// Bitmask of options used in `CCCryptorCreate()`.
+pub type CCOptions = u32;
+
+/// Perform PKCS#7 padding.
+#[allow(non_upper_case_globals)]
+pub const kCCOptionPKCS7Padding: CCOptions = 0x0001;
+/// Electronic Code Book mode. The default is CBC.
+#[allow(non_upper_case_globals)]
+pub const kCCOptionECBMode: CCOptions = 0x0002;
+
+/// Block cipher mode of operation used in `CCCryptorCreateWithMode()`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub enum CCMode {
+    /// Electronic Code Book
+    kCCModeECB = 1,
+    /// Cipher Block Chaining
+    kCCModeCBC = 2,
+    /// Cipher Feedback
+    kCCModeCFB = 3,
+    /// Counter
+    kCCModeCTR = 4,
+    /// Output Feedback
+    kCCModeOFB = 7,
+    /// XEX-based Tweaked CodeBook with CipherText Stealing
+    kCCModeXTS = 8,
+    /// RC4 stream cipher
+    kCCModeRC4 = 9,
+    /// Cipher Feedback, 8 bit
+    kCCModeCFB8 = 10,
+    /// Galois/Counter Mode
+    kCCModeGCM = 11,
+    /// Counter with CBC-MAC
+    kCCModeCCM = 12,
+}
+
+/// Padding used in `CCCryptorCreateWithMode()`.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub enum CCPadding {
+    /// No padding
+    ccNoPadding = 0,
+    /// PKCS#7 padding
+    ccPKCS7Padding = 1,
+}
+
+/// Bitmask of mode options used in `CCCryptorCreateWithMode()`.
+pub type CCModeOptions = u32;
+
+/// Big-endian counter for CTR mode.
+#[allow(non_upper_case_globals)]
+pub const kCCModeOptionCTR_BE: CCModeOptions = 0x0002;
+
+/// Number of bytes for an AES-128 key.
+#[allow(non_upper_case_globals)]
+pub const kCCKeySizeAES128: usize = 16;
+/// Number of bytes for an AES-192 key.
+#[allow(non_upper_case_globals)]
+pub const kCCKeySizeAES192: usize = 24;
+/// Number of bytes for an AES-256 key.
+#[allow(non_upper_case_globals)]
+pub const kCCKeySizeAES256: usize = 32;
+/// Number of bytes for a DES key.
+#[allow(non_upper_case_globals)]
+pub const kCCKeySizeDES: usize = 8;
+/// Number of bytes for a Triple-DES key.
+#[allow(non_upper_case_globals)]
+pub const kCCKeySize3DES: usize = 24;
+
+/// Number of bytes in an AES block.
+#[allow(non_upper_case_globals)]
+pub const kCCBlockSizeAES128: usize = 16;
+/// Number of bytes in a DES block.
+#[allow(non_upper_case_globals)]
+pub const kCCBlockSizeDES: usize = 8;
+/// Number of bytes in a Triple-DES block.
+#[allow(non_upper_case_globals)]
+pub const kCCBlockSize3DES: usize = 8;
+/// Number of bytes in a CAST block.
+#[allow(non_upper_case_globals)]
+pub const kCCBlockSizeCAST: usize = 8;
+/// Number of bytes in an RC2 block.
+#[allow(non_upper_case_globals)]
+pub const kCCBlockSizeRC2: usize = 8;
+/// Number of bytes in a Blowfish block.
+#[allow(non_upper_case_globals)]
+pub const kCCBlockSizeBlowfish: usize = 8;
+
+/// Opaque cryptographic context used in `CCCryptor*()` functions.
+#[repr(C)]
+pub struct CCCryptor {
+    _private: [u8; 0],
+}
+
+/// Reference to a `CCCryptor`.
+pub type CCCryptorRef = *mut CCCryptor;
+
 extern "C" {
+    /// Initializes MD2 hasher. See `man 3cc CC_MD5` for details.
+    pub fn CC_MD2_Init(ctx: *mut CC_MD2_CTX) -> c_int;
+    /// Appends data to be hashed. See `man 3cc CC_MD5` for details.
+    pub fn CC_MD2_Update(ctx: *mut CC_MD2_CTX, data: *const u8, n: CC_LONG) -> c_int;
+    /// Generates MD2 hash. See `man 3cc CC_MD5` for details.
+    pub fn CC_MD2_Final(md: *mut u8, ctx: *mut CC_MD2_CTX) -> c_int;
+    /// Generates the MD2 hash of `data` into `md`, and returns `md`. See `man 3cc CC_MD5` for
+    /// details.
+    pub fn CC_MD2(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
+    /// Initializes MD4 hasher. See `man 3cc CC_MD5` for details.
+    pub fn CC_MD4_Init(ctx: *mut CC_MD4_CTX) -> c_int;
+    /// Appends data to be hashed. See `man 3cc CC_MD5` for details.
+    pub fn CC_MD4_Update(ctx: *mut CC_MD4_CTX, data: *const u8, n: CC_LONG) -> c_int;
+    /// Generates MD4 hash. See `man 3cc CC_MD5` for details.
+    pub fn CC_MD4_Final(md: *mut u8, ctx: *mut CC_MD4_CTX) -> c_int;
+    /// Generates the MD4 hash of `data` into `md`, and returns `md`. See `man 3cc CC_MD5` for
+    /// details.
+    pub fn CC_MD4(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
     /// Initializes MD5 hasher. See `man 3cc CC_MD5` for details.
     pub fn CC_MD5_Init(ctx: *mut CC_MD5_CTX) -> c_int;
     /// Appends data to be hashed. See `man 3cc CC_MD5` for details.
-    pub fn CC_MD5_Update(ctx: *mut CC_MD5_CTX, data: *const u8, n: usize) -> c_int;
+    pub fn CC_MD5_Update(ctx: *mut CC_MD5_CTX, data: *const u8, n: CC_LONG) -> c_int;
     /// Generates MD5 hash. See `man 3cc CC_MD5` for details.
     pub fn CC_MD5_Final(md: *mut u8, ctx: *mut CC_MD5_CTX) -> c_int;
+    /// Generates the MD5 hash of `data` into `md`, and returns `md`. See `man 3cc CC_MD5` for
+    /// details.
+    pub fn CC_MD5(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
     /// Initializes SHA1 hasher. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA1_Init(ctx: *mut CC_SHA_CTX) -> c_int;
     /// Appends data to be hashed. See `man 3cc CC_SHA` for details.
-    pub fn CC_SHA1_Update(ctx: *mut CC_SHA_CTX, data: *const u8, n: usize) -> c_int;
+    pub fn CC_SHA1_Update(ctx: *mut CC_SHA_CTX, data: *const u8, n: CC_LONG) -> c_int;
     /// Generates SHA1 hash. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA1_Final(md: *mut u8, ctx: *mut CC_SHA_CTX) -> c_int;
+    /// Generates the SHA1 hash of `data` into `md`, and returns `md`. See `man 3cc CC_SHA` for
+    /// details.
+    pub fn CC_SHA1(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
+    /// Initializes SHA224 hasher. See `man 3cc CC_SHA` for details.
+    pub fn CC_SHA224_Init(ctx: *mut CC_SHA256_CTX) -> c_int;
+    /// Appends data to be hashed. See `man 3cc CC_SHA` for details.
+    pub fn CC_SHA224_Update(ctx: *mut CC_SHA256_CTX, data: *const u8, n: CC_LONG) -> c_int;
+    /// Generates SHA224 hash. See `man 3cc CC_SHA` for details.
+    pub fn CC_SHA224_Final(md: *mut u8, ctx: *mut CC_SHA256_CTX) -> c_int;
+    /// Generates the SHA224 hash of `data` into `md`, and returns `md`. See `man 3cc CC_SHA` for
+    /// details.
+    pub fn CC_SHA224(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
     /// Initializes SHA256 hasher. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA256_Init(ctx: *mut CC_SHA256_CTX) -> c_int;
     /// Appends data to be hashed. See `man 3cc CC_SHA` for details.
-    pub fn CC_SHA256_Update(ctx: *mut CC_SHA256_CTX, data: *const u8, n: usize) -> c_int;
+    pub fn CC_SHA256_Update(ctx: *mut CC_SHA256_CTX, data: *const u8, n: CC_LONG) -> c_int;
     /// Generates SHA256 hash. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA256_Final(md: *mut u8, ctx: *mut CC_SHA256_CTX) -> c_int;
+    /// Generates the SHA256 hash of `data` into `md`, and returns `md`. See `man 3cc CC_SHA` for
+    /// details.
+    pub fn CC_SHA256(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
     /// Initializes SHA384 hasher. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA384_Init(ctx: *mut CC_SHA512_CTX) -> c_int;
     /// Appends data to be hashed. See `man 3cc CC_SHA` for details.
-    pub fn CC_SHA384_Update(ctx: *mut CC_SHA512_CTX, data: *const u8, n: usize) -> c_int;
+    pub fn CC_SHA384_Update(ctx: *mut CC_SHA512_CTX, data: *const u8, n: CC_LONG) -> c_int;
     /// Generates SHA384 hash. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA384_Final(md: *mut u8, ctx: *mut CC_SHA512_CTX) -> c_int;
+    /// Generates the SHA384 hash of `data` into `md`, and returns `md`. See `man 3cc CC_SHA` for
+    /// details.
+    pub fn CC_SHA384(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
     /// Initializes SHA512 hasher. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA512_Init(ctx: *mut CC_SHA512_CTX) -> c_int;
     /// Appends data to be hashed. See `man 3cc CC_SHA` for details.
-    pub fn CC_SHA512_Update(ctx: *mut CC_SHA512_CTX, data: *const u8, n: usize) -> c_int;
+    pub fn CC_SHA512_Update(ctx: *mut CC_SHA512_CTX, data: *const u8, n: CC_LONG) -> c_int;
     /// Generates SHA512 hash. See `man 3cc CC_SHA` for details.
     pub fn CC_SHA512_Final(md: *mut u8, ctx: *mut CC_SHA512_CTX) -> c_int;
+    /// Generates the SHA512 hash of `data` into `md`, and returns `md`. See `man 3cc CC_SHA` for
+    /// details.
+    pub fn CC_SHA512(data: *const u8, n: CC_LONG, md: *mut u8) -> *mut u8;
     /// Generic digest hasher.
     pub fn CCDigest(
         algorithm: CCDigestAlgorithm,
@@ -206,10 +730,16 @@ extern "C" {
     ) -> c_int;
     /// Allocate and initialize a `CCDigestCtx` for a digest.
     pub fn CCDigestCreate(algorithm: CCDigestAlgorithm) -> *mut CCDigestCtx;
+    /// Allocate and initialize a `CCDigestCtx` for the digest with the given DER-encoded object
+    /// identifier. Returns null if there is no such digest.
+    pub fn CCDigestCreateByOID(oid: *const u8, oid_len: usize) -> *mut CCDigestCtx;
     /// Continue to digest data. Returns `0` on success.
     pub fn CCDigestUpdate(ctx: *mut CCDigestCtx, data: *const u8, length: usize) -> c_int;
     /// Conclude digest operations and produce the digest output. Returns `0` on success.
     pub fn CCDigestFinal(ctx: *mut CCDigestCtx, output: *mut u8) -> c_int;
+    /// Allocate a new `CCDigestCtx` which is a copy of the given one, including the data
+    /// processed so far.
+    pub fn CCDigestClone(ctx: *mut CCDigestCtx) -> *mut CCDigestCtx;
     /// Clear and free a `CCDigestCtx`.
     pub fn CCDigestDestroy(ctx: *mut CCDigestCtx);
     /// Clear and re-initialize a `CCDigestCtx` for the same algorithm.
@@ -224,6 +754,12 @@ extern "C" {
     pub fn CCDigestGetBlockSizeFromRef(ctx: *mut CCDigestCtx) -> usize;
     /// Provides the digest output size of the digest algorithm. Returns `0` on failure.
     pub fn CCDigestGetOutputSizeFromRef(ctx: *mut CCDigestCtx) -> usize;
+    /// Provides the length of the DER-encoded object identifier of the digest algorithm. Returns
+    /// `0` if it has none.
+    pub fn CCDigestOIDLen(ctx: *mut CCDigestCtx) -> usize;
+    /// Writes the DER-encoded object identifier of the digest algorithm to `oid`, which must be
+    /// `CCDigestOIDLen` bytes long.
+    pub fn CCDigestOID(ctx: *mut CCDigestCtx, oid: *mut u8);
 
     /// Derive a key from a user-supplied password via PBKDF2.
     pub fn CCKeyDerivationPBKDF(
@@ -237,4 +773,171 @@ extern "C" {
         derivedKey: *mut u8,
         derivedKeyLen: usize,
     ) -> c_int;
+    /// Determine the number of PRF rounds to use for a specific delay on the current platform.
+    /// Returns `u32::MAX` (`-1`) on failure.
+    pub fn CCCalibratePBKDF(
+        algorithm: CCPBKDFAlgorithm,
+        passwordLen: usize,
+        saltLen: usize,
+        prf: CCPseudoRandomAlgorithm,
+        derivedKeyLen: usize,
+        msec: u32,
+    ) -> c_uint;
+
+    /// The default random number generator, for use with `CCRandomCopyBytes()`.
+    pub static kCCRandomDefault: CCRandomRef;
+
+    /// Fill `bytes` with random data from the given generator. Returns `0` on success.
+    /// Deprecated in favor of `CCRandomGenerateBytes()`.
+    pub fn CCRandomCopyBytes(rnd: CCRandomRef, bytes: *mut u8, count: usize) -> c_int;
+    /// Fill `bytes` with cryptographically strong random data. Returns `0` on success.
+    pub fn CCRandomGenerateBytes(bytes: *mut u8, count: usize) -> c_int;
+
+    /// Initialize a `CCHmacContext` with the provided key and algorithm.
+    pub fn CCHmacInit(
+        ctx: *mut CCHmacContext,
+        algorithm: CCHmacAlgorithm,
+        key: *const u8,
+        keyLength: usize,
+    );
+    /// Process some data.
+    pub fn CCHmacUpdate(ctx: *mut CCHmacContext, data: *const u8, dataLength: usize);
+    /// Obtain the final Message Authentication Code.
+    pub fn CCHmacFinal(ctx: *mut CCHmacContext, macOut: *mut u8);
+    /// Stateless, one-shot HMAC function.
+    pub fn CCHmac(
+        algorithm: CCHmacAlgorithm,
+        key: *const u8,
+        keyLength: usize,
+        data: *const u8,
+        dataLength: usize,
+        macOut: *mut u8,
+    );
+
+    /// Create a cryptographic context. Returns `0` on success.
+    pub fn CCCryptorCreate(
+        op: CCOperation,
+        alg: CCAlgorithm,
+        options: CCOptions,
+        key: *const u8,
+        keyLength: usize,
+        iv: *const u8,
+        cryptorRef: *mut CCCryptorRef,
+    ) -> c_int;
+    /// Create a cryptographic context using the given block cipher mode. Returns `0` on success.
+    pub fn CCCryptorCreateWithMode(
+        op: CCOperation,
+        mode: CCMode,
+        alg: CCAlgorithm,
+        padding: CCPadding,
+        iv: *const u8,
+        key: *const u8,
+        keyLength: usize,
+        tweak: *const u8,
+        tweakLength: usize,
+        numRounds: c_int,
+        options: CCModeOptions,
+        cryptorRef: *mut CCCryptorRef,
+    ) -> c_int;
+    /// Free a context created by `CCCryptorCreate*()`. Returns `0` on success.
+    pub fn CCCryptorRelease(cryptorRef: CCCryptorRef) -> c_int;
+    /// Process (encrypt or decrypt) some data. Returns `0` on success.
+    pub fn CCCryptorUpdate(
+        cryptorRef: CCCryptorRef,
+        dataIn: *const u8,
+        dataInLength: usize,
+        dataOut: *mut u8,
+        dataOutAvailable: usize,
+        dataOutMoved: *mut usize,
+    ) -> c_int;
+    /// Finish an encrypt or decrypt operation. Returns `0` on success.
+    pub fn CCCryptorFinal(
+        cryptorRef: CCCryptorRef,
+        dataOut: *mut u8,
+        dataOutAvailable: usize,
+        dataOutMoved: *mut usize,
+    ) -> c_int;
+    /// Determine the output buffer size required to process the given input size.
+    pub fn CCCryptorGetOutputLength(
+        cryptorRef: CCCryptorRef,
+        inputLength: usize,
+        final_: bool,
+    ) -> usize;
+    /// Reinitialize an existing `CCCryptorRef` with a (possibly) new initialization vector.
+    /// Returns `0` on success.
+    pub fn CCCryptorReset(cryptorRef: CCCryptorRef, iv: *const u8) -> c_int;
+    /// Add the initialization vector to a GCM `CCCryptorRef`. Returns `0` on success.
+    pub fn CCCryptorGCMAddIV(cryptorRef: CCCryptorRef, iv: *const u8, ivLen: usize) -> c_int;
+    /// Add additional authenticated data to a GCM `CCCryptorRef`. Returns `0` on success.
+    pub fn CCCryptorGCMAddAAD(cryptorRef: CCCryptorRef, aData: *const u8, aDataLen: usize)
+        -> c_int;
+    /// Encrypt data with a GCM `CCCryptorRef`. Returns `0` on success.
+    pub fn CCCryptorGCMEncrypt(
+        cryptorRef: CCCryptorRef,
+        dataIn: *const u8,
+        dataInLength: usize,
+        dataOut: *mut u8,
+    ) -> c_int;
+    /// Decrypt data with a GCM `CCCryptorRef`. Returns `0` on success.
+    pub fn CCCryptorGCMDecrypt(
+        cryptorRef: CCCryptorRef,
+        dataIn: *const u8,
+        dataInLength: usize,
+        dataOut: *mut u8,
+    ) -> c_int;
+    /// Produce the authentication tag of a GCM `CCCryptorRef`. The tag is not verified when
+    /// decrypting. Returns `0` on success.
+    pub fn CCCryptorGCMFinal(
+        cryptorRef: CCCryptorRef,
+        tagOut: *mut u8,
+        tagLength: *mut usize,
+    ) -> c_int;
+    /// Reset a GCM `CCCryptorRef` so that it can be used with a new initialization vector.
+    /// Returns `0` on success.
+    pub fn CCCryptorGCMReset(cryptorRef: CCCryptorRef) -> c_int;
+    /// Stateless, one-shot GCM encryption. Returns `0` on success.
+    pub fn CCCryptorGCMOneshotEncrypt(
+        alg: CCAlgorithm,
+        key: *const u8,
+        keyLength: usize,
+        iv: *const u8,
+        ivLength: usize,
+        aData: *const u8,
+        aDataLength: usize,
+        dataIn: *const u8,
+        dataInLength: usize,
+        dataOut: *mut u8,
+        tagOut: *mut u8,
+        tagLength: usize,
+    ) -> c_int;
+    /// Stateless, one-shot GCM decryption, which also verifies the authentication tag.
+    /// Returns `0` on success.
+    pub fn CCCryptorGCMOneshotDecrypt(
+        alg: CCAlgorithm,
+        key: *const u8,
+        keyLength: usize,
+        iv: *const u8,
+        ivLength: usize,
+        aData: *const u8,
+        aDataLength: usize,
+        dataIn: *const u8,
+        dataInLength: usize,
+        dataOut: *mut u8,
+        tagIn: *const u8,
+        tagLength: usize,
+    ) -> c_int;
+    /// Stateless, one-shot encrypt or decrypt operation. Returns `0` on success.
+    pub fn CCCrypt(
+        op: CCOperation,
+        alg: CCAlgorithm,
+        options: CCOptions,
+        key: *const u8,
+        keyLength: usize,
+        iv: *const u8,
+        dataIn: *const u8,
+        dataInLength: usize,
+        dataOut: *mut u8,
+        dataOutAvailable: usize,
+        dataOutMoved: *mut usize,
+    ) -> c_int;
 }
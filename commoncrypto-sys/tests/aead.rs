@@ -0,0 +1,79 @@
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+extern crate commoncrypto_sys;
+extern crate hex;
+
+// These values come from test case 4 in "The Galois/Counter Mode of Operation (GCM)"
+const KEY: &str = "feffe9928665731c6d6a8f9467308308";
+const NONCE: &str = "cafebabefacedbaddecaf888";
+const AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+const PLAINTEXT: &str = concat!(
+    "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72",
+    "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39"
+);
+const CIPHERTEXT: &str = concat!(
+    "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e",
+    "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091"
+);
+const TAG: &str = "5bc94fbc3221a5db94fae95ae7121a47";
+
+#[test]
+fn aes_gcm_oneshot_encrypt() {
+    let key = hex::decode(KEY).unwrap();
+    let nonce = hex::decode(NONCE).unwrap();
+    let aad = hex::decode(AAD).unwrap();
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let mut ciphertext = vec![0u8; plaintext.len()];
+    let mut tag = [0u8; 16];
+    unsafe {
+        assert_eq!(
+            0,
+            commoncrypto_sys::CCCryptorGCMOneshotEncrypt(
+                commoncrypto_sys::CCAlgorithm::kCCAlgorithmAES,
+                key.as_ptr(),
+                key.len(),
+                nonce.as_ptr(),
+                nonce.len(),
+                aad.as_ptr(),
+                aad.len(),
+                plaintext.as_ptr(),
+                plaintext.len(),
+                ciphertext.as_mut_ptr(),
+                tag.as_mut_ptr(),
+                tag.len()
+            )
+        );
+    }
+    assert_eq!(CIPHERTEXT, hex::encode(ciphertext));
+    assert_eq!(TAG, hex::encode(tag));
+}
+
+#[test]
+fn aes_gcm_oneshot_decrypt() {
+    let key = hex::decode(KEY).unwrap();
+    let nonce = hex::decode(NONCE).unwrap();
+    let aad = hex::decode(AAD).unwrap();
+    let ciphertext = hex::decode(CIPHERTEXT).unwrap();
+    let tag = hex::decode(TAG).unwrap();
+    let mut plaintext = vec![0u8; ciphertext.len()];
+    unsafe {
+        assert_eq!(
+            0,
+            commoncrypto_sys::CCCryptorGCMOneshotDecrypt(
+                commoncrypto_sys::CCAlgorithm::kCCAlgorithmAES,
+                key.as_ptr(),
+                key.len(),
+                nonce.as_ptr(),
+                nonce.len(),
+                aad.as_ptr(),
+                aad.len(),
+                ciphertext.as_ptr(),
+                ciphertext.len(),
+                plaintext.as_mut_ptr(),
+                tag.as_ptr(),
+                tag.len()
+            )
+        );
+    }
+    assert_eq!(PLAINTEXT, hex::encode(plaintext));
+}
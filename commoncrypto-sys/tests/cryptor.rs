@@ -0,0 +1,109 @@
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+extern crate commoncrypto_sys;
+extern crate hex;
+
+use std::ptr;
+
+// These values come from the AES-128 ECB test vectors in NIST SP 800-38A, Appendix F.1.1
+const KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+const PLAINTEXT: &str = "6bc1bee22e409f96e93d7e117393172a";
+const CIPHERTEXT: &str = "3ad77bb40d7a3660a89ecaf32466ef97";
+
+macro_rules! test_cccryptor {
+    (
+        $test_name: ident,
+        $op: ident,
+        $input: ident,
+        $expected_output: ident
+    ) => {
+        #[test]
+        fn $test_name() {
+            let key = hex::decode(KEY).expect("key from hex");
+            let input = hex::decode($input).expect("input from hex");
+            let mut cryptor: commoncrypto_sys::CCCryptorRef = ptr::null_mut();
+            let mut output = vec![0u8; commoncrypto_sys::kCCBlockSizeAES128];
+            let mut moved = 0;
+            unsafe {
+                assert_eq!(
+                    0,
+                    commoncrypto_sys::CCCryptorCreate(
+                        commoncrypto_sys::CCOperation::$op,
+                        commoncrypto_sys::CCAlgorithm::kCCAlgorithmAES,
+                        commoncrypto_sys::kCCOptionECBMode,
+                        key.as_ptr(),
+                        key.len(),
+                        ptr::null(),
+                        &mut cryptor
+                    )
+                );
+                assert_eq!(
+                    output.len(),
+                    commoncrypto_sys::CCCryptorGetOutputLength(cryptor, input.len(), false)
+                );
+                assert_eq!(
+                    0,
+                    commoncrypto_sys::CCCryptorUpdate(
+                        cryptor,
+                        input.as_ptr(),
+                        input.len(),
+                        output.as_mut_ptr(),
+                        output.len(),
+                        &mut moved
+                    )
+                );
+                assert_eq!(output.len(), moved);
+                assert_eq!(
+                    0,
+                    commoncrypto_sys::CCCryptorFinal(cryptor, ptr::null_mut(), 0, &mut moved)
+                );
+                assert_eq!(0, moved);
+                assert_eq!(0, commoncrypto_sys::CCCryptorRelease(cryptor));
+            }
+            assert_eq!($expected_output, hex::encode(output));
+        }
+    };
+}
+
+test_cccryptor!(aes_ecb_encrypt, kCCEncrypt, PLAINTEXT, CIPHERTEXT);
+test_cccryptor!(aes_ecb_decrypt, kCCDecrypt, CIPHERTEXT, PLAINTEXT);
+
+macro_rules! test_cccrypt {
+    (
+        $test_name: ident,
+        $op: ident,
+        $input: ident,
+        $expected_output: ident
+    ) => {
+        #[test]
+        fn $test_name() {
+            let key = hex::decode(KEY).expect("key from hex");
+            let input = hex::decode($input).expect("input from hex");
+            let mut output = vec![0u8; commoncrypto_sys::kCCBlockSizeAES128];
+            let mut moved = 0;
+            unsafe {
+                assert_eq!(
+                    0,
+                    commoncrypto_sys::CCCrypt(
+                        commoncrypto_sys::CCOperation::$op,
+                        commoncrypto_sys::CCAlgorithm::kCCAlgorithmAES,
+                        commoncrypto_sys::kCCOptionECBMode,
+                        key.as_ptr(),
+                        key.len(),
+                        ptr::null(),
+                        input.as_ptr(),
+                        input.len(),
+                        output.as_mut_ptr(),
+                        output.len(),
+                        &mut moved
+                    )
+                );
+            }
+            assert_eq!(output.len(), moved);
+            assert_eq!($expected_output, hex::encode(output));
+        }
+    };
+}
+
+test_cccrypt!(aes_ecb_encrypt_oneshot, kCCEncrypt, PLAINTEXT, CIPHERTEXT);
+test_cccrypt!(aes_ecb_decrypt_oneshot, kCCDecrypt, CIPHERTEXT, PLAINTEXT);
@@ -1,9 +1,16 @@
+#![allow(clippy::redundant_static_lifetimes)]
+
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
 extern crate commoncrypto_sys;
 extern crate hex;
 
 const TO_HASH: &'static str = "The quick brown fox jumps over the lazy dog";
+const TO_HASH_MD2: &str = "03d85a0d629d2c442e987525319fc471";
+const TO_HASH_MD4: &str = "1bee69a46ba811185c194762abaeae90";
 const TO_HASH_MD5: &'static str = "9e107d9d372bb6826bd81d3542a419d6";
 const TO_HASH_SHA1: &'static str = "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12";
+const TO_HASH_SHA224: &str = "730e109bd7a8a32b1cb9d9a09aa2325d2430587ddbc0c38bad911525";
 const TO_HASH_SHA256: &'static str = concat!(
     "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb76",
     "2d02d0bf37c9e592"
@@ -34,7 +41,11 @@ macro_rules! test_cc_hash {
             unsafe {
                 assert_eq!(commoncrypto_sys::$init_func(&mut ctx), 1);
                 assert_eq!(
-                    commoncrypto_sys::$update_func(&mut ctx, TO_HASH.as_ptr(), TO_HASH.len()),
+                    commoncrypto_sys::$update_func(
+                        &mut ctx,
+                        TO_HASH.as_ptr(),
+                        TO_HASH.len() as commoncrypto_sys::CC_LONG
+                    ),
                     1
                 );
                 assert_eq!(commoncrypto_sys::$final_func(md.as_mut_ptr(), &mut ctx), 1);
@@ -44,6 +55,24 @@ macro_rules! test_cc_hash {
     };
 }
 
+macro_rules! test_cc_oneshot {
+    ($test_name: ident, $digest_len: ident, $func: ident, $expected_hash: ident) => {
+        #[test]
+        fn $test_name() {
+            let mut md = [0u8; commoncrypto_sys::$digest_len];
+            unsafe {
+                let result = commoncrypto_sys::$func(
+                    TO_HASH.as_ptr(),
+                    TO_HASH.len() as commoncrypto_sys::CC_LONG,
+                    md.as_mut_ptr(),
+                );
+                assert_eq!(result, md.as_mut_ptr());
+            }
+            assert_eq!(hex::encode(md), $expected_hash);
+        }
+    };
+}
+
 macro_rules! test_ccdigest {
     (
         $test_name: ident,
@@ -87,10 +116,32 @@ macro_rules! test_ccdigestgetoutputsize {
                     $expected_digest_len
                 );
             }
+            assert_eq!(
+                CCDigestAlgorithm::$algorithm.output_len(),
+                $expected_digest_len
+            );
         }
     };
 }
 
+test_cc_hash!(
+    md2_hash,
+    CC_MD2_CTX,
+    MD2_DIGEST_LENGTH,
+    CC_MD2_Init,
+    CC_MD2_Update,
+    CC_MD2_Final,
+    TO_HASH_MD2
+);
+test_cc_hash!(
+    md4_hash,
+    CC_MD4_CTX,
+    MD4_DIGEST_LENGTH,
+    CC_MD4_Init,
+    CC_MD4_Update,
+    CC_MD4_Final,
+    TO_HASH_MD4
+);
 test_cc_hash!(
     md5_hash,
     CC_MD5_CTX,
@@ -109,6 +160,15 @@ test_cc_hash!(
     CC_SHA1_Final,
     TO_HASH_SHA1
 );
+test_cc_hash!(
+    sha224_hash,
+    CC_SHA256_CTX,
+    SHA224_DIGEST_LENGTH,
+    CC_SHA224_Init,
+    CC_SHA224_Update,
+    CC_SHA224_Final,
+    TO_HASH_SHA224
+);
 test_cc_hash!(
     sha256_hash,
     CC_SHA256_CTX,
@@ -137,6 +197,37 @@ test_cc_hash!(
     TO_HASH_SHA512
 );
 
+test_cc_oneshot!(md2_oneshot, MD2_DIGEST_LENGTH, CC_MD2, TO_HASH_MD2);
+test_cc_oneshot!(md4_oneshot, MD4_DIGEST_LENGTH, CC_MD4, TO_HASH_MD4);
+test_cc_oneshot!(md5_oneshot, MD5_DIGEST_LENGTH, CC_MD5, TO_HASH_MD5);
+test_cc_oneshot!(sha1_oneshot, SHA1_DIGEST_LENGTH, CC_SHA1, TO_HASH_SHA1);
+test_cc_oneshot!(
+    sha224_oneshot,
+    SHA224_DIGEST_LENGTH,
+    CC_SHA224,
+    TO_HASH_SHA224
+);
+test_cc_oneshot!(
+    sha256_oneshot,
+    SHA256_DIGEST_LENGTH,
+    CC_SHA256,
+    TO_HASH_SHA256
+);
+test_cc_oneshot!(
+    sha384_oneshot,
+    SHA384_DIGEST_LENGTH,
+    CC_SHA384,
+    TO_HASH_SHA384
+);
+test_cc_oneshot!(
+    sha512_oneshot,
+    SHA512_DIGEST_LENGTH,
+    CC_SHA512,
+    TO_HASH_SHA512
+);
+
+test_ccdigest!(md2_ccdigest, kCCDigestMD2, MD2_DIGEST_LENGTH, TO_HASH_MD2);
+test_ccdigest!(md4_ccdigest, kCCDigestMD4, MD4_DIGEST_LENGTH, TO_HASH_MD4);
 test_ccdigest!(md5_ccdigest, kCCDigestMD5, MD5_DIGEST_LENGTH, TO_HASH_MD5);
 test_ccdigest!(
     sha1_ccdigest,
@@ -180,3 +271,106 @@ test_ccdigestgetoutputsize!(
     kCCDigestSHA512,
     SHA512_DIGEST_LENGTH
 );
+
+#[test]
+fn block_sizes() {
+    use commoncrypto_sys::{
+        CCDigestAlgorithm, CCDigestGetBlockSize, MD4_BLOCK_BYTES, MD5_BLOCK_BYTES,
+        SHA1_BLOCK_BYTES, SHA224_BLOCK_BYTES, SHA256_BLOCK_BYTES, SHA384_BLOCK_BYTES,
+        SHA512_BLOCK_BYTES,
+    };
+    let block_sizes = [
+        (CCDigestAlgorithm::kCCDigestMD4, MD4_BLOCK_BYTES),
+        (CCDigestAlgorithm::kCCDigestMD5, MD5_BLOCK_BYTES),
+        (CCDigestAlgorithm::kCCDigestSHA1, SHA1_BLOCK_BYTES),
+        (CCDigestAlgorithm::kCCDigestSHA224, SHA224_BLOCK_BYTES),
+        (CCDigestAlgorithm::kCCDigestSHA256, SHA256_BLOCK_BYTES),
+        (CCDigestAlgorithm::kCCDigestSHA384, SHA384_BLOCK_BYTES),
+        (CCDigestAlgorithm::kCCDigestSHA512, SHA512_BLOCK_BYTES),
+    ];
+    for &(algorithm, block_size) in block_sizes.iter() {
+        assert_eq!(unsafe { CCDigestGetBlockSize(algorithm) }, block_size);
+    }
+}
+
+#[test]
+fn ccdigestclone() {
+    use commoncrypto_sys::{
+        CCDigestAlgorithm, CCDigestClone, CCDigestCreate, CCDigestDestroy, CCDigestFinal,
+        CCDigestUpdate, MD5_DIGEST_LENGTH,
+    };
+    let mut md = [0u8; MD5_DIGEST_LENGTH];
+    let mut cloned_md = [0u8; MD5_DIGEST_LENGTH];
+    unsafe {
+        let ctx = CCDigestCreate(CCDigestAlgorithm::kCCDigestMD5);
+        assert_eq!(CCDigestUpdate(ctx, TO_HASH.as_ptr(), 10), 0);
+        let cloned = CCDigestClone(ctx);
+        assert!(!cloned.is_null());
+        for ctx in &[ctx, cloned] {
+            assert_eq!(
+                CCDigestUpdate(*ctx, TO_HASH[10..].as_ptr(), TO_HASH.len() - 10),
+                0
+            );
+        }
+        assert_eq!(CCDigestFinal(ctx, md.as_mut_ptr()), 0);
+        assert_eq!(CCDigestFinal(cloned, cloned_md.as_mut_ptr()), 0);
+        CCDigestDestroy(ctx);
+        CCDigestDestroy(cloned);
+    }
+    assert_eq!(hex::encode(md), TO_HASH_MD5);
+    assert_eq!(hex::encode(cloned_md), TO_HASH_MD5);
+}
+
+#[test]
+fn ccdigestcreatebyoid() {
+    use commoncrypto_sys::{
+        CCDigestAlgorithm, CCDigestCreateByOID, CCDigestDestroy, CCDigestFinal,
+        CCDigestGetOutputSizeFromRef, CCDigestOID, CCDigestOIDLen, CCDigestUpdate,
+        SHA256_DIGEST_LENGTH,
+    };
+    let oid = CCDigestAlgorithm::kCCDigestSHA256.oid().unwrap();
+    assert_eq!(hex::encode(oid), "0609608648016503040201");
+    assert_eq!(
+        CCDigestAlgorithm::from_oid(oid),
+        Some(CCDigestAlgorithm::kCCDigestSHA256)
+    );
+    assert_eq!(CCDigestAlgorithm::from_oid(&oid[..10]), None);
+    assert_eq!(CCDigestAlgorithm::kCCDigestRMD320.oid(), None);
+
+    let mut md = [0u8; SHA256_DIGEST_LENGTH];
+    let mut ctx_oid = [0u8; 11];
+    unsafe {
+        let ctx = CCDigestCreateByOID(oid.as_ptr(), oid.len());
+        assert!(!ctx.is_null());
+        assert_eq!(CCDigestGetOutputSizeFromRef(ctx), SHA256_DIGEST_LENGTH);
+        assert_eq!(CCDigestOIDLen(ctx), oid.len());
+        CCDigestOID(ctx, ctx_oid.as_mut_ptr());
+        assert_eq!(CCDigestUpdate(ctx, TO_HASH.as_ptr(), TO_HASH.len()), 0);
+        assert_eq!(CCDigestFinal(ctx, md.as_mut_ptr()), 0);
+        CCDigestDestroy(ctx);
+
+        assert!(CCDigestCreateByOID(oid.as_ptr(), 10).is_null());
+    }
+    assert_eq!(ctx_oid, oid);
+    assert_eq!(hex::encode(md), TO_HASH_SHA256);
+}
+
+#[test]
+fn digest_algorithm_names() {
+    use commoncrypto_sys::CCDigestAlgorithm;
+    for algorithm in CCDigestAlgorithm::all() {
+        assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+    }
+    assert_eq!(CCDigestAlgorithm::kCCDigestSHA256.to_string(), "SHA-256");
+    assert_eq!("sha256".parse(), Ok(CCDigestAlgorithm::kCCDigestSHA256));
+    assert_eq!("SHA_512".parse(), Ok(CCDigestAlgorithm::kCCDigestSHA512));
+    assert_eq!("md5".parse(), Ok(CCDigestAlgorithm::kCCDigestMD5));
+    assert_eq!("rmd160".parse(), Ok(CCDigestAlgorithm::kCCDigestRMD160));
+    assert_eq!("RIPEMD160".parse(), Ok(CCDigestAlgorithm::kCCDigestRMD160));
+    assert!("none".parse::<CCDigestAlgorithm>().is_err());
+    assert!("sha3-256".parse::<CCDigestAlgorithm>().is_err());
+    assert_eq!(
+        CCDigestAlgorithm::all().count(),
+        CCDigestAlgorithm::ALL.len()
+    );
+}
@@ -0,0 +1,56 @@
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+extern crate commoncrypto_sys;
+extern crate hex;
+
+// These keys, data and MACs come from the test vectors stated in RFC 2202 and RFC 4231
+const KEY: &str = "Jefe";
+const DATA: &str = "what do ya want for nothing?";
+const MAC_MD5: &str = "750c783e6ab0b503eaa86e310a5db738";
+const MAC_SHA1: &str = "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79";
+const MAC_SHA256: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+
+macro_rules! test_cchmac {
+    (
+        $test_name: ident,
+        $algorithm: ident,
+        $mac_len: ident,
+        $expected_mac: ident
+    ) => {
+        #[test]
+        fn $test_name() {
+            let mut ctx = commoncrypto_sys::CCHmacContext::default();
+            let mut mac = [0u8; commoncrypto_sys::$mac_len];
+            let mut oneshot_mac = [0u8; commoncrypto_sys::$mac_len];
+            unsafe {
+                commoncrypto_sys::CCHmacInit(
+                    &mut ctx,
+                    commoncrypto_sys::CCHmacAlgorithm::$algorithm,
+                    KEY.as_ptr(),
+                    KEY.len(),
+                );
+                commoncrypto_sys::CCHmacUpdate(&mut ctx, DATA.as_ptr(), DATA.len());
+                commoncrypto_sys::CCHmacFinal(&mut ctx, mac.as_mut_ptr());
+                commoncrypto_sys::CCHmac(
+                    commoncrypto_sys::CCHmacAlgorithm::$algorithm,
+                    KEY.as_ptr(),
+                    KEY.len(),
+                    DATA.as_ptr(),
+                    DATA.len(),
+                    oneshot_mac.as_mut_ptr(),
+                );
+            }
+            assert_eq!(hex::encode(mac), $expected_mac);
+            assert_eq!(hex::encode(oneshot_mac), $expected_mac);
+        }
+    };
+}
+
+test_cchmac!(md5_hmac, kCCHmacAlgMD5, MD5_DIGEST_LENGTH, MAC_MD5);
+test_cchmac!(sha1_hmac, kCCHmacAlgSHA1, SHA1_DIGEST_LENGTH, MAC_SHA1);
+test_cchmac!(
+    sha256_hmac,
+    kCCHmacAlgSHA256,
+    SHA256_DIGEST_LENGTH,
+    MAC_SHA256
+);
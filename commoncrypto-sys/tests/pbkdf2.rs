@@ -1,3 +1,7 @@
+#![allow(clippy::redundant_static_lifetimes)]
+
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
 extern crate commoncrypto_sys;
 extern crate hex;
 
@@ -52,3 +56,19 @@ test_pbkdf2!(
     4096,
     DERIVED4096
 );
+
+#[test]
+fn calibrate_pbkdf2() {
+    let rounds = unsafe {
+        commoncrypto_sys::CCCalibratePBKDF(
+            commoncrypto_sys::CCPBKDFAlgorithm::kCCPBKDF2,
+            PASSWORD.len(),
+            SALT.len(),
+            commoncrypto_sys::CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1,
+            20,
+            10,
+        )
+    };
+    assert!(rounds > 0);
+    assert!(rounds < u32::MAX);
+}
@@ -0,0 +1,31 @@
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+extern crate commoncrypto_sys;
+
+#[test]
+fn random_generate_bytes() {
+    let mut buf = [0u8; 64];
+    unsafe {
+        assert_eq!(
+            0,
+            commoncrypto_sys::CCRandomGenerateBytes(buf.as_mut_ptr(), buf.len())
+        );
+    }
+    assert!(buf.iter().any(|&b| b != 0));
+}
+
+#[test]
+fn random_copy_bytes() {
+    let mut buf = [0u8; 64];
+    unsafe {
+        assert_eq!(
+            0,
+            commoncrypto_sys::CCRandomCopyBytes(
+                commoncrypto_sys::kCCRandomDefault,
+                buf.as_mut_ptr(),
+                buf.len()
+            )
+        );
+    }
+    assert!(buf.iter().any(|&b| b != 0));
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Idiomatic Rust wrapper for `CommonCrypto`'s AES-GCM authenticated encryption.
+
+use commoncrypto_sys::{
+    kCCKeySizeAES128, kCCKeySizeAES192, kCCKeySizeAES256, CCAlgorithm, CCCryptorGCMOneshotDecrypt,
+    CCCryptorGCMOneshotEncrypt,
+};
+use error::check_status;
+use secret::{wipe, SecretBytes};
+use std::fmt;
+use {Error, Result};
+
+/// Number of bytes in an AES-GCM authentication tag.
+pub const TAG_LEN: usize = 16;
+
+/// AES in Galois/Counter Mode, an authenticated encryption with associated data (AEAD) cipher.
+pub struct AesGcm {
+    key: SecretBytes,
+}
+
+impl AesGcm {
+    /// Creates a new `AesGcm` with the given 128, 192 or 256-bit `key`.
+    pub fn new(key: &[u8]) -> Result<AesGcm> {
+        if [kCCKeySizeAES128, kCCKeySizeAES192, kCCKeySizeAES256].contains(&key.len()) {
+            Ok(AesGcm {
+                key: SecretBytes::from_slice(key),
+            })
+        } else {
+            Err(Error::KeySizeError)
+        }
+    }
+
+    /// Encrypts `buffer` in place, authenticating it along with `aad`, and returns the
+    /// authentication tag.
+    pub fn encrypt_in_place_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<[u8; TAG_LEN]> {
+        if nonce.is_empty() {
+            return Err(Error::ParamError);
+        }
+        let mut tag = [0u8; TAG_LEN];
+        let data = buffer.as_mut_ptr();
+        check_status(unsafe {
+            CCCryptorGCMOneshotEncrypt(
+                CCAlgorithm::kCCAlgorithmAES,
+                self.key.as_ptr(),
+                self.key.len(),
+                nonce.as_ptr(),
+                nonce.len(),
+                aad.as_ptr(),
+                aad.len(),
+                data,
+                buffer.len(),
+                data,
+                tag.as_mut_ptr(),
+                TAG_LEN,
+            )
+        })?;
+        Ok(tag)
+    }
+
+    /// Decrypts `buffer` in place, and checks `tag` against it and `aad`. `CommonCrypto` verifies
+    /// the tag, in constant time.
+    ///
+    /// If the tag does not match, `buffer` is zeroed and `Error::DecodeError` is returned.
+    pub fn decrypt_in_place_detached(
+        &self,
+        nonce: &[u8],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<()> {
+        if tag.len() != TAG_LEN || nonce.is_empty() {
+            return Err(Error::ParamError);
+        }
+        let data = buffer.as_mut_ptr();
+        let result = check_status(unsafe {
+            CCCryptorGCMOneshotDecrypt(
+                CCAlgorithm::kCCAlgorithmAES,
+                self.key.as_ptr(),
+                self.key.len(),
+                nonce.as_ptr(),
+                nonce.len(),
+                aad.as_ptr(),
+                aad.len(),
+                data,
+                buffer.len(),
+                data,
+                tag.as_ptr(),
+                TAG_LEN,
+            )
+        });
+        if result.is_err() {
+            wipe(buffer);
+        }
+        result
+    }
+
+    /// Encrypts `plaintext`, authenticating it along with `aad`. The authentication tag is
+    /// appended to the returned ciphertext.
+    pub fn encrypt(&self, nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut buffer = plaintext.to_vec();
+        let tag = self.encrypt_in_place_detached(nonce, aad, &mut buffer)?;
+        buffer.extend_from_slice(&tag);
+        Ok(buffer)
+    }
+
+    /// Decrypts `ciphertext`, which has the authentication tag appended to it, and checks the tag
+    /// against it and `aad`. See `decrypt_in_place_detached`.
+    pub fn decrypt(&self, nonce: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        if ciphertext.len() < TAG_LEN {
+            return Err(Error::DecodeError);
+        }
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - TAG_LEN);
+        let mut buffer = ciphertext.to_vec();
+        self.decrypt_in_place_detached(nonce, aad, &mut buffer, tag)?;
+        Ok(buffer)
+    }
+}
+
+impl fmt::Debug for AesGcm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AesGcm").finish()
+    }
+}
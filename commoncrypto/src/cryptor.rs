@@ -0,0 +1,358 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Idiomatic Rust wrapper for `CommonCrypto`'s `CCCryptor` struct.
+
+use commoncrypto_sys::{
+    kCCBlockSize3DES, kCCBlockSizeAES128, kCCBlockSizeBlowfish, kCCBlockSizeCAST, kCCBlockSizeDES,
+    kCCBlockSizeRC2, CCCrypt, CCCryptorCreate, CCCryptorCreateWithMode, CCCryptorFinal,
+    CCCryptorGetOutputLength, CCCryptorRef, CCCryptorRelease, CCCryptorReset, CCCryptorUpdate,
+};
+use error::check_status;
+use std::os::raw::c_int;
+use std::ptr;
+use {Error, Result};
+
+pub use commoncrypto_sys::{
+    kCCModeOptionCTR_BE, kCCOptionECBMode, kCCOptionPKCS7Padding, CCAlgorithm, CCMode,
+    CCModeOptions, CCOperation, CCOptions, CCPadding,
+};
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+enum State {
+    Reset,
+    Updated,
+    Finalized,
+}
+
+fn block_size(algorithm: CCAlgorithm) -> usize {
+    match algorithm {
+        CCAlgorithm::kCCAlgorithmAES => kCCBlockSizeAES128,
+        CCAlgorithm::kCCAlgorithmDES => kCCBlockSizeDES,
+        CCAlgorithm::kCCAlgorithm3DES => kCCBlockSize3DES,
+        CCAlgorithm::kCCAlgorithmCAST => kCCBlockSizeCAST,
+        CCAlgorithm::kCCAlgorithmRC4 => 0,
+        CCAlgorithm::kCCAlgorithmRC2 => kCCBlockSizeRC2,
+        CCAlgorithm::kCCAlgorithmBlowfish => kCCBlockSizeBlowfish,
+    }
+}
+
+fn check_iv(algorithm: CCAlgorithm, iv: Option<&[u8]>) -> Result<()> {
+    match iv {
+        Some(iv) if iv.len() != block_size(algorithm) => Err(Error::ParamError),
+        _ => Ok(()),
+    }
+}
+
+fn iv_ptr(iv: Option<&[u8]>) -> *const u8 {
+    iv.map_or(ptr::null(), <[u8]>::as_ptr)
+}
+
+/// Encrypts or decrypts data with a symmetric cipher.
+///
+/// Once an operation is finished, the cryptor must be given a new IV with `reset` before it is
+/// used again, so that a key and IV pair is never reused.
+#[derive(Debug)]
+pub struct Cryptor {
+    cryptor: CCCryptorRef,
+    algorithm: CCAlgorithm,
+    state: State,
+}
+
+// The cryptor is owned by the Cryptor, and is only used through &mut self.
+unsafe impl Send for Cryptor {}
+unsafe impl Sync for Cryptor {}
+
+impl Cryptor {
+    /// Creates a new `Cryptor` which performs `op` using the given `algorithm` and `options`.
+    ///
+    /// Unless `kCCOptionECBMode` is set, block ciphers use CBC mode with the given `iv`
+    /// (all zeros if `None`). For other modes, use `with_mode`.
+    pub fn new(
+        op: CCOperation,
+        algorithm: CCAlgorithm,
+        options: CCOptions,
+        key: &[u8],
+        iv: Option<&[u8]>,
+    ) -> Result<Cryptor> {
+        check_iv(algorithm, iv)?;
+        let mut cryptor: CCCryptorRef = ptr::null_mut();
+        check_status(unsafe {
+            CCCryptorCreate(
+                op,
+                algorithm,
+                options,
+                key.as_ptr(),
+                key.len(),
+                iv_ptr(iv),
+                &mut cryptor,
+            )
+        })?;
+        Ok(Cryptor {
+            cryptor,
+            algorithm,
+            state: State::Reset,
+        })
+    }
+
+    /// Creates a new `Cryptor` which performs `op` using the given `algorithm` in the given block
+    /// cipher `mode`.
+    ///
+    /// For counter mode, `iv` is the initial counter block and `options` should contain
+    /// `kCCModeOptionCTR_BE`.
+    pub fn with_mode(
+        op: CCOperation,
+        mode: CCMode,
+        algorithm: CCAlgorithm,
+        padding: CCPadding,
+        key: &[u8],
+        iv: Option<&[u8]>,
+        options: CCModeOptions,
+    ) -> Result<Cryptor> {
+        check_iv(algorithm, iv)?;
+        let mut cryptor: CCCryptorRef = ptr::null_mut();
+        check_status(unsafe {
+            CCCryptorCreateWithMode(
+                op,
+                mode,
+                algorithm,
+                padding,
+                iv_ptr(iv),
+                key.as_ptr(),
+                key.len(),
+                ptr::null(),
+                0,
+                0,
+                options,
+                &mut cryptor,
+            )
+        })?;
+        Ok(Cryptor {
+            cryptor,
+            algorithm,
+            state: State::Reset,
+        })
+    }
+
+    /// Starts a new operation with the same key and the given `iv` (all zeros if `None`),
+    /// discarding any data fed in so far.
+    ///
+    /// The IV should not have been used with the key before: in CBC mode, reusing it reveals
+    /// whether messages start with the same blocks, and in counter and feedback modes, it reuses
+    /// the keystream.
+    pub fn reset(&mut self, iv: Option<&[u8]>) -> Result<()> {
+        check_iv(self.algorithm, iv)?;
+        check_status(unsafe { CCCryptorReset(self.cryptor, iv_ptr(iv)) })?;
+        self.state = State::Reset;
+        Ok(())
+    }
+
+    /// Feeds data into the cryptor, returning whatever output is available so far.
+    ///
+    /// Returns `Error::CallSequenceError` if the operation was finished and the cryptor has not
+    /// been `reset` since.
+    pub fn update(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        if self.state == State::Finalized {
+            return Err(Error::CallSequenceError);
+        }
+        let expected_len = unsafe { CCCryptorGetOutputLength(self.cryptor, data.len(), false) };
+        let mut out = vec![0; expected_len];
+        let mut moved = 0;
+        check_status(unsafe {
+            CCCryptorUpdate(
+                self.cryptor,
+                data.as_ptr(),
+                data.len(),
+                out.as_mut_ptr(),
+                out.len(),
+                &mut moved,
+            )
+        })?;
+        self.state = State::Updated;
+        out.truncate(moved);
+        Ok(out)
+    }
+
+    /// Finishes the encryption or decryption operation and produces the remaining output,
+    /// including any padding.
+    ///
+    /// Returns `Error::CallSequenceError` if the operation was already finished and the cryptor
+    /// has not been `reset` since.
+    pub fn finish(&mut self) -> Result<Vec<u8>> {
+        if self.state == State::Finalized {
+            return Err(Error::CallSequenceError);
+        }
+        let expected_len = unsafe { CCCryptorGetOutputLength(self.cryptor, 0, true) };
+        let mut out = vec![0; expected_len];
+        let mut moved = 0;
+        check_status(unsafe {
+            CCCryptorFinal(self.cryptor, out.as_mut_ptr(), out.len(), &mut moved)
+        })?;
+        self.state = State::Finalized;
+        out.truncate(moved);
+        Ok(out)
+    }
+}
+
+impl Drop for Cryptor {
+    fn drop(&mut self) {
+        unsafe { CCCryptorRelease(self.cryptor) };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn cccrypt(
+    op: CCOperation,
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+    out: &mut [u8],
+    moved: &mut usize,
+) -> Result<c_int> {
+    check_iv(algorithm, iv)?;
+    Ok(unsafe {
+        CCCrypt(
+            op,
+            algorithm,
+            options,
+            key.as_ptr(),
+            key.len(),
+            iv_ptr(iv),
+            data.as_ptr(),
+            data.len(),
+            out.as_mut_ptr(),
+            out.len(),
+            moved,
+        )
+    })
+}
+
+fn crypt(
+    op: CCOperation,
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    let mut out = vec![0; data.len() + block_size(algorithm)];
+    let mut moved = 0;
+    let mut result = cccrypt(op, algorithm, options, key, iv, data, &mut out, &mut moved)?;
+    if Error::from_status(result) == Error::BufferTooSmall {
+        // On kCCBufferTooSmall, dataOutMoved contains the required output size
+        out.resize(moved, 0);
+        result = cccrypt(op, algorithm, options, key, iv, data, &mut out, &mut moved)?;
+    }
+    check_status(result)?;
+    out.truncate(moved);
+    Ok(out)
+}
+
+fn crypt_into(
+    op: CCOperation,
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+    out: &mut [u8],
+) -> Result<usize> {
+    let mut moved = 0;
+    check_status(cccrypt(
+        op, algorithm, options, key, iv, data, out, &mut moved,
+    )?)?;
+    Ok(moved)
+}
+
+/// Encrypts `data` in one call, using the given `algorithm` and `options`.
+///
+/// Unless `kCCOptionECBMode` is set, block ciphers use CBC mode with the given `iv`
+/// (all zeros if `None`). `CCCrypt` supports no other modes: for counter or feedback modes, use
+/// `Cryptor::with_mode`.
+pub fn encrypt(
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    crypt(CCOperation::kCCEncrypt, algorithm, options, key, iv, data)
+}
+
+/// Decrypts `data` in one call, using the given `algorithm` and `options`.
+///
+/// Unless `kCCOptionECBMode` is set, block ciphers use CBC mode with the given `iv`
+/// (all zeros if `None`). `CCCrypt` supports no other modes: for counter or feedback modes, use
+/// `Cryptor::with_mode`.
+pub fn decrypt(
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    crypt(CCOperation::kCCDecrypt, algorithm, options, key, iv, data)
+}
+
+/// Encrypts `data` into `out` in one call, returning the number of bytes written.
+///
+/// See `encrypt` for details on the parameters.
+pub fn encrypt_into(
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+    out: &mut [u8],
+) -> Result<usize> {
+    crypt_into(
+        CCOperation::kCCEncrypt,
+        algorithm,
+        options,
+        key,
+        iv,
+        data,
+        out,
+    )
+}
+
+/// Decrypts `data` into `out` in one call, returning the number of bytes written.
+///
+/// See `decrypt` for details on the parameters.
+pub fn decrypt_into(
+    algorithm: CCAlgorithm,
+    options: CCOptions,
+    key: &[u8],
+    iv: Option<&[u8]>,
+    data: &[u8],
+    out: &mut [u8],
+) -> Result<usize> {
+    crypt_into(
+        CCOperation::kCCDecrypt,
+        algorithm,
+        options,
+        key,
+        iv,
+        data,
+        out,
+    )
+}
@@ -0,0 +1,126 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Base64 and Base32 (RFC 4648, standard alphabets) codecs, shared by the modules which encode
+//! binary data as text.
+
+use {Error, Result};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes `data` as standard Base64, padded with `=` if `padded`.
+pub fn base64_encode(data: &[u8], padded: bool) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (i, byte)| {
+            bits | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if padded {
+            for _ in chunk.len()..3 {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Decodes standard Base64, which must be padded with `=` if `padded`, and unpadded otherwise.
+///
+/// Encodings with non-zero trailing bits are rejected, so that each input has one encoding.
+// usize::is_multiple_of is newer than the oldest supported Rust.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn base64_decode(encoded: &str, padded: bool) -> Result<Vec<u8>> {
+    let encoded = if padded {
+        let unpadded = encoded.trim_end_matches('=');
+        if encoded.len() % 4 != 0 || encoded.len() - unpadded.len() > 2 {
+            return Err(Error::DecodeError);
+        }
+        unpadded
+    } else {
+        encoded
+    };
+    if encoded.len() % 4 == 1 {
+        return Err(Error::DecodeError);
+    }
+    let mut decoded = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.as_bytes().chunks(4) {
+        let mut bits = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .ok_or(Error::DecodeError)?;
+            bits |= (value as u32) << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            decoded.push((bits >> (16 - 8 * i)) as u8);
+        }
+        if bits << (8 * (chunk.len() - 1)) & 0x00ff_ffff != 0 {
+            return Err(Error::DecodeError);
+        }
+    }
+    Ok(decoded)
+}
+
+/// Encodes `data` as unpadded Base32.
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity((data.len() * 8).div_ceil(5));
+    for chunk in data.chunks(5) {
+        let bits = chunk.iter().enumerate().fold(0u64, |bits, (i, byte)| {
+            bits | u64::from(*byte) << (32 - 8 * i)
+        });
+        for i in 0..(chunk.len() * 8).div_ceil(5) {
+            encoded.push(BASE32_ALPHABET[(bits >> (35 - 5 * i) & 0x1f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decodes unpadded, upper-case Base32.
+///
+/// Encodings with non-zero trailing bits are rejected, so that each input has one encoding.
+pub fn base32_decode(encoded: &str) -> Result<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    for c in encoded.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or(Error::DecodeError)?;
+        bits = bits << 5 | value as u32;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            decoded.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+    // A whole character left over, or set bits, cannot have come from the encoder
+    if bit_count >= 5 || bits != 0 {
+        return Err(Error::DecodeError);
+    }
+    Ok(decoded)
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Error type for `CommonCrypto` status codes.
+
+use commoncrypto_sys::CCCryptorStatus;
+use std::error;
+use std::fmt;
+use std::io;
+use std::os::raw::c_int;
+use std::result;
+
+/// Errors reported by `CommonCrypto`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Illegal parameter value (`kCCParamError`)
+    ParamError,
+    /// Insufficent buffer provided for specified operation (`kCCBufferTooSmall`)
+    BufferTooSmall,
+    /// Memory allocation failure (`kCCMemoryFailure`)
+    MemoryFailure,
+    /// Input size was not aligned properly (`kCCAlignmentError`)
+    AlignmentError,
+    /// Input data did not decode or decrypt properly (`kCCDecodeError`)
+    DecodeError,
+    /// Function not implemented for the current algorithm (`kCCUnimplemented`)
+    Unimplemented,
+    /// Operation would overflow (`kCCOverflow`)
+    Overflow,
+    /// Random number generator failure (`kCCRNGFailure`)
+    RngFailure,
+    /// Unspecified error (`kCCUnspecifiedError`)
+    UnspecifiedError,
+    /// Functions called in the wrong order (`kCCCallSequenceError`)
+    CallSequenceError,
+    /// Invalid key size for the algorithm (`kCCKeySizeError`)
+    KeySizeError,
+    /// Key is not valid (`kCCInvalidKey`)
+    InvalidKey,
+    /// A nonzero status code that is not documented by `CommonCrypto`
+    Unknown(i32),
+}
+
+/// Result type returned by the `CommonCrypto` wrappers.
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    /// Converts a nonzero status code returned by a `CommonCrypto` function.
+    pub fn from_status(status: c_int) -> Error {
+        match CCCryptorStatus::from_raw(status) {
+            Some(CCCryptorStatus::kCCParamError) => Error::ParamError,
+            Some(CCCryptorStatus::kCCBufferTooSmall) => Error::BufferTooSmall,
+            Some(CCCryptorStatus::kCCMemoryFailure) => Error::MemoryFailure,
+            Some(CCCryptorStatus::kCCAlignmentError) => Error::AlignmentError,
+            Some(CCCryptorStatus::kCCDecodeError) => Error::DecodeError,
+            Some(CCCryptorStatus::kCCUnimplemented) => Error::Unimplemented,
+            Some(CCCryptorStatus::kCCOverflow) => Error::Overflow,
+            Some(CCCryptorStatus::kCCRNGFailure) => Error::RngFailure,
+            Some(CCCryptorStatus::kCCUnspecifiedError) => Error::UnspecifiedError,
+            Some(CCCryptorStatus::kCCCallSequenceError) => Error::CallSequenceError,
+            Some(CCCryptorStatus::kCCKeySizeError) => Error::KeySizeError,
+            Some(CCCryptorStatus::kCCInvalidKey) => Error::InvalidKey,
+            Some(CCCryptorStatus::kCCSuccess) | None => Error::Unknown(status),
+        }
+    }
+}
+
+/// Converts a status code returned by a `CommonCrypto` function into a `Result`.
+pub fn check_status(status: c_int) -> Result<()> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(Error::from_status(status))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::ParamError => write!(f, "illegal parameter value"),
+            Error::BufferTooSmall => write!(f, "insufficent buffer provided for operation"),
+            Error::MemoryFailure => write!(f, "memory allocation failure"),
+            Error::AlignmentError => write!(f, "input size was not aligned properly"),
+            Error::DecodeError => write!(f, "input data did not decode or decrypt properly"),
+            Error::Unimplemented => write!(f, "function not implemented for the algorithm"),
+            Error::Overflow => write!(f, "operation would overflow"),
+            Error::RngFailure => write!(f, "random number generator failure"),
+            Error::UnspecifiedError => write!(f, "unspecified error"),
+            Error::CallSequenceError => write!(f, "functions called in the wrong order"),
+            Error::KeySizeError => write!(f, "invalid key size for the algorithm"),
+            Error::InvalidKey => write!(f, "key is not valid"),
+            Error::Unknown(status) => write!(f, "unknown CommonCrypto status: {}", status),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        let kind = match err {
+            Error::ParamError
+            | Error::BufferTooSmall
+            | Error::AlignmentError
+            | Error::KeySizeError
+            | Error::InvalidKey => io::ErrorKind::InvalidInput,
+            Error::DecodeError => io::ErrorKind::InvalidData,
+            Error::MemoryFailure => io::ErrorKind::OutOfMemory,
+            Error::Unimplemented => io::ErrorKind::Unsupported,
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, err)
+    }
+}
@@ -19,24 +19,60 @@
 // THE SOFTWARE.
 
 //! Idiomatic Rust wrapper for `CommonCrypto`'s `CCDigestCtx` struct.
+//!
+//! With the `digest` feature enabled, this module also provides per-algorithm hashers (`Md5`,
+//! `Sha1`, `Sha224`, `Sha256`, `Sha384` and `Sha512`) which implement the `RustCrypto` `digest`
+//! traits, so that they can be used with crates such as `hmac` and `hkdf`.
 
 use commoncrypto_sys::{
-    CCDigestCreate, CCDigestCtx, CCDigestDestroy, CCDigestFinal, CCDigestGetOutputSizeFromRef,
-    CCDigestReset, CCDigestUpdate,
+    CCDigestClone, CCDigestCreate, CCDigestCreateByOID, CCDigestCtx, CCDigestDestroy,
+    CCDigestFinal, CCDigestGetBlockSize, CCDigestGetDigest, CCDigestGetOutputSize,
+    CCDigestGetOutputSizeFromRef, CCDigestOID, CCDigestOIDLen, CCDigestReset, CCDigestUpdate,
 };
+use error::check_status;
+use secret::wipe;
 use std::io;
+use {Error, Result};
 
 pub use commoncrypto_sys::CCDigestAlgorithm;
 
+mod adapters;
+mod algorithm;
+mod context;
+mod ctx;
+#[cfg(feature = "digest")]
+mod digest;
+mod digest_info;
+mod oneshot;
+mod output;
+mod pool;
+mod resumable;
+
+pub use self::adapters::{hash_reader, HashingReader, HashingWriter};
+pub use self::algorithm::{algorithms, Algorithm};
+pub use self::context::{FinishError, HashContext};
+
+#[cfg(feature = "digest")]
+pub use self::digest::{Md5, Sha1, Sha224, Sha256, Sha384, Sha512};
+pub use self::digest_info::digest_info;
+pub use self::oneshot::{md5, sha1, sha224, sha256, sha384, sha512};
+pub use self::output::{
+    Digest, Md5Digest, Sha1Digest, Sha224Digest, Sha256Digest, Sha384Digest, Sha512Digest,
+};
+pub use self::pool::{HasherPool, PooledHasher};
+pub use self::resumable::{
+    HashState, ResumableMd5, ResumableSha1, ResumableSha224, ResumableSha256, ResumableSha384,
+    ResumableSha512,
+};
+
 const MAX_DIGEST_SIZE: usize = 64;
 
-macro_rules! err_from_ccdigest_retval {
-    ($func_name: expr, $val: expr) => {
-        Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("{} returned nonzero: {}", $func_name, $val),
-        ))
-    };
+/// Whether `CommonCrypto` supports the given digest `algorithm` on the current host.
+///
+/// `kCCDigestNone` is never supported, and newer OS versions have dropped some algorithms, such as
+/// Skein.
+pub fn is_supported(algorithm: CCDigestAlgorithm) -> bool {
+    unsafe { CCDigestGetOutputSize(algorithm) != 0 && CCDigestGetBlockSize(algorithm) != 0 }
 }
 
 #[derive(PartialEq, Copy, Clone, Debug)]
@@ -47,65 +83,168 @@ enum State {
 }
 
 /// Generates cryptographic hashes.
+///
+/// Once the digest is finished, the hasher starts over: data fed into it afterwards begins a new
+/// digest, and finishing it again without feeding it any data produces the digest of no data.
+/// `HashContext` rules out this reuse at compile time.
+///
+/// A `Hasher` can be moved to another thread, as its digest context has no thread affinity. It
+/// cannot be shared between threads, because `CommonCrypto` reads the context without
+/// synchronization. To reuse hashers across many short-lived tasks, see `HasherPool`.
 #[derive(Debug)]
 pub struct Hasher {
     ctx: *mut CCDigestCtx,
     state: State,
 }
 
+// The digest context is owned by the Hasher, and has no thread affinity. It is not Sync: clone and
+// oid pass it to CommonCrypto through &self.
+unsafe impl Send for Hasher {}
+
 impl Hasher {
     /// Creates a new `Hasher` which will use the given cryptographic `algorithm`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the algorithm is not supported, or the digest context cannot be allocated. See
+    /// `try_new`.
     pub fn new(algorithm: CCDigestAlgorithm) -> Hasher {
-        let ctx: *mut CCDigestCtx;
-        unsafe {
-            ctx = CCDigestCreate(algorithm);
+        match Hasher::try_new(algorithm) {
+            Ok(hasher) => hasher,
+            Err(err) => panic!("CCDigestCreate failed for {:?}: {}", algorithm, err),
         }
-        Hasher {
+    }
+
+    /// Creates a new `Hasher` which will use the given cryptographic `algorithm`.
+    ///
+    /// Returns `Error::Unimplemented` if the algorithm is not supported (see `is_supported`), and
+    /// `Error::MemoryFailure` if the digest context cannot be allocated.
+    pub fn try_new(algorithm: CCDigestAlgorithm) -> Result<Hasher> {
+        if !is_supported(algorithm) {
+            return Err(Error::Unimplemented);
+        }
+        let ctx = unsafe { CCDigestCreate(algorithm) };
+        if ctx.is_null() {
+            return Err(Error::MemoryFailure);
+        }
+        Ok(Hasher {
+            ctx,
+            state: State::Reset,
+        })
+    }
+
+    /// Creates a new `Hasher` which will use the algorithm with the given DER-encoded ASN.1 object
+    /// identifier, such as one from `CCDigestAlgorithm::oid`.
+    ///
+    /// Returns `Error::Unimplemented` if there is no supported algorithm with the identifier.
+    pub fn from_oid(oid: &[u8]) -> Result<Hasher> {
+        let ctx = unsafe { CCDigestCreateByOID(oid.as_ptr(), oid.len()) };
+        if ctx.is_null() {
+            return Err(Error::Unimplemented);
+        }
+        Ok(Hasher {
             ctx,
             state: State::Reset,
+        })
+    }
+
+    /// The DER-encoded ASN.1 object identifier of the algorithm, if it has one.
+    pub fn oid(&self) -> Option<Vec<u8>> {
+        let len = unsafe { CCDigestOIDLen(self.ctx) };
+        if len == 0 {
+            return None;
         }
+        let mut oid = vec![0; len];
+        unsafe { CCDigestOID(self.ctx, oid.as_mut_ptr()) };
+        Some(oid)
     }
 
     fn init(&mut self) {
-        match self.state {
-            State::Reset => return,
-            State::Updated => {
-                let _ = self.finish();
-            }
-            State::Finalized => (),
+        if self.state != State::Reset {
+            unsafe { CCDigestReset(self.ctx) };
+            self.state = State::Reset;
         }
-        unsafe { CCDigestReset(self.ctx) };
-        self.state = State::Reset;
     }
 
-    /// Feeds data into the hasher.
-    pub fn update(&mut self, data: &[u8]) -> io::Result<usize> {
+    /// Feeds data into the hasher. If the digest was finished, this starts a new digest.
+    pub fn update(&mut self, data: &[u8]) -> Result<usize> {
         if self.state == State::Finalized {
             self.init();
         }
-        let result = unsafe { CCDigestUpdate(self.ctx, data.as_ptr() as *mut _, data.len()) };
-        if result == 0 {
-            self.state = State::Updated;
-            Ok(data.len())
-        } else {
-            err_from_ccdigest_retval!("CCDigestCreate", result)
-        }
+        check_status(unsafe { CCDigestUpdate(self.ctx, data.as_ptr() as *mut _, data.len()) })?;
+        self.state = State::Updated;
+        Ok(data.len())
     }
 
-    /// Finalizes digest operations and produces the digest output.
-    pub fn finish(&mut self) -> io::Result<Vec<u8>> {
+    /// Produces the digest output for the data fed in so far, without finalizing the digest
+    /// operations. More data can be fed into the hasher afterwards.
+    pub fn peek(&mut self) -> Result<Vec<u8>> {
         if self.state == State::Finalized {
             self.init();
         }
         let expected_len = unsafe { CCDigestGetOutputSizeFromRef(self.ctx) };
-        let mut md = vec![0; MAX_DIGEST_SIZE];
-        let result = unsafe { CCDigestFinal(self.ctx, md.as_mut_ptr()) };
-        if result == 0 {
-            self.state = State::Finalized;
-            md.truncate(expected_len);
-            Ok(md)
-        } else {
-            err_from_ccdigest_retval!("CCDigestFinal", result)
+        let mut md = [0; MAX_DIGEST_SIZE];
+        check_status(unsafe { CCDigestGetDigest(self.ctx, md.as_mut_ptr()) })?;
+        let digest = md[..expected_len].to_vec();
+        wipe(&mut md);
+        Ok(digest)
+    }
+
+    fn finish_raw(&mut self, md: &mut [u8]) -> Result<usize> {
+        let expected_len = unsafe { CCDigestGetOutputSizeFromRef(self.ctx) };
+        if md.len() < expected_len {
+            return Err(Error::BufferTooSmall);
+        }
+        if self.state == State::Finalized {
+            self.init();
+        }
+        check_status(unsafe { CCDigestFinal(self.ctx, md.as_mut_ptr()) })?;
+        self.state = State::Finalized;
+        Ok(expected_len)
+    }
+
+    /// Finalizes digest operations and produces the digest output.
+    pub fn finish(&mut self) -> Result<Vec<u8>> {
+        let mut md = [0; MAX_DIGEST_SIZE];
+        let len = self.finish_raw(&mut md)?;
+        let digest = md[..len].to_vec();
+        wipe(&mut md);
+        Ok(digest)
+    }
+
+    /// Finalizes digest operations and writes the digest output to the start of `out`, without
+    /// allocating. Returns the number of bytes written.
+    ///
+    /// If `out` is shorter than the output length of the algorithm, `Error::BufferTooSmall` is
+    /// returned and the digest operations are not finalized.
+    pub fn finish_into(&mut self, out: &mut [u8]) -> Result<usize> {
+        self.finish_raw(out)
+    }
+
+    /// Finalizes digest operations and produces the digest output, without allocating.
+    ///
+    /// The algorithm of a `Hasher` is chosen at runtime, so `N` is checked at runtime too: it must
+    /// be the output length of the algorithm (see `CCDigestAlgorithm::output_len`), otherwise
+    /// `Error::ParamError` is returned and the digest operations are not finalized. The hashers
+    /// for a single algorithm, such as `ResumableSha256`, tie the length to their type instead.
+    pub fn finish_digest<const N: usize>(&mut self) -> Result<Digest<N>> {
+        if unsafe { CCDigestGetOutputSizeFromRef(self.ctx) } != N {
+            return Err(Error::ParamError);
+        }
+        let mut bytes = [0; N];
+        self.finish_raw(&mut bytes)?;
+        Ok(Digest::from(bytes))
+    }
+}
+
+impl Clone for Hasher {
+    /// Copies the hasher, including the data fed in so far.
+    fn clone(&self) -> Hasher {
+        let ctx = unsafe { CCDigestClone(self.ctx) };
+        assert!(!ctx.is_null(), "CCDigestClone failed");
+        Hasher {
+            ctx,
+            state: self.state,
         }
     }
 }
@@ -113,7 +252,7 @@ impl Hasher {
 impl io::Write for Hasher {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        self.update(buf)
+        Ok(self.update(buf)?)
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -123,9 +262,7 @@ impl io::Write for Hasher {
 
 impl Drop for Hasher {
     fn drop(&mut self) {
-        if self.state != State::Finalized {
-            let _ = self.finish();
-        }
+        self.init();
         unsafe { CCDigestDestroy(self.ctx) }
     }
 }
@@ -0,0 +1,247 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! I/O adapters which hash data as it passes through them.
+//!
+//! Besides the `std::io` traits, the adapters implement the `tokio` I/O traits with the `tokio`
+//! feature, and the `futures-io` traits with the `futures-io` feature.
+
+use super::{CCDigestAlgorithm, Hasher};
+use std::io::{self, Read, Write};
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+use std::pin::Pin;
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+use std::task::{Context, Poll};
+use Result;
+
+/// Size of the buffer used by `hash_reader`. Large reads amortize the cost of each call into
+/// `CommonCrypto`, and the size is a multiple of the block size of every digest algorithm.
+const HASH_READER_BUF_SIZE: usize = 64 * 1024;
+
+/// Reader which hashes the data read from the inner reader.
+///
+/// As an asynchronous reader, the inner reader must be `Unpin`. Other readers can be wrapped with
+/// `Box::pin`.
+#[derive(Debug)]
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R> HashingReader<R> {
+    /// Creates a new `HashingReader` which hashes the data read from `inner` with the given
+    /// `algorithm`.
+    pub fn new(algorithm: CCDigestAlgorithm, inner: R) -> HashingReader<R> {
+        HashingReader {
+            inner,
+            hasher: Hasher::new(algorithm),
+        }
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader. Data read directly from it is not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Finalizes digest operations, returning the inner reader and the digest of the data read
+    /// so far.
+    pub fn finish(mut self) -> Result<(R, Vec<u8>)> {
+        let digest = self.hasher.finish()?;
+        Ok((self.inner, digest))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read])?;
+        Ok(read)
+    }
+}
+
+/// Writer which hashes the data written to the inner writer.
+///
+/// As an asynchronous writer, the inner writer must be `Unpin`. Other writers can be wrapped with
+/// `Box::pin`.
+#[derive(Debug)]
+pub struct HashingWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W> HashingWriter<W> {
+    /// Creates a new `HashingWriter` which hashes the data written to `inner` with the given
+    /// `algorithm`.
+    pub fn new(algorithm: CCDigestAlgorithm, inner: W) -> HashingWriter<W> {
+        HashingWriter {
+            inner,
+            hasher: Hasher::new(algorithm),
+        }
+    }
+
+    /// Gets a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner writer. Data written directly to it is not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Finalizes digest operations, returning the inner writer and the digest of the data
+    /// written so far. The inner writer is not flushed.
+    pub fn finish(mut self) -> Result<(W, Vec<u8>)> {
+        let digest = self.hasher.finish()?;
+        Ok((self.inner, digest))
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written])?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Hashes everything read from `reader` with the given `algorithm`.
+pub fn hash_reader<R: Read>(algorithm: CCDigestAlgorithm, mut reader: R) -> io::Result<Vec<u8>> {
+    let mut hasher = Hasher::new(algorithm);
+    let mut buf = vec![0; HASH_READER_BUF_SIZE];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => {
+                hasher.update(&buf[..read])?;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(hasher.finish()?)
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+fn hash_written<W>(
+    writer: &mut HashingWriter<W>,
+    buf: &[u8],
+    poll: Poll<io::Result<usize>>,
+) -> Poll<io::Result<usize>> {
+    match poll {
+        Poll::Ready(Ok(written)) => Poll::Ready(
+            writer
+                .hasher
+                .update(&buf[..written])
+                .map_err(io::Error::from),
+        ),
+        poll => poll,
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: ::tokio::io::AsyncRead + Unpin> ::tokio::io::AsyncRead for HashingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ::tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let reader = self.get_mut();
+        let filled = buf.filled().len();
+        match Pin::new(&mut reader.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(
+                reader
+                    .hasher
+                    .update(&buf.filled()[filled..])
+                    .map(|_| ())
+                    .map_err(io::Error::from),
+            ),
+            poll => poll,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<W: ::tokio::io::AsyncWrite + Unpin> ::tokio::io::AsyncWrite for HashingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let writer = self.get_mut();
+        let poll = Pin::new(&mut writer.inner).poll_write(cx, buf);
+        hash_written(writer, buf, poll)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<R: ::futures_io::AsyncRead + Unpin> ::futures_io::AsyncRead for HashingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let reader = self.get_mut();
+        match Pin::new(&mut reader.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(read)) => {
+                Poll::Ready(reader.hasher.update(&buf[..read]).map_err(io::Error::from))
+            }
+            poll => poll,
+        }
+    }
+}
+
+#[cfg(feature = "futures-io")]
+impl<W: ::futures_io::AsyncWrite + Unpin> ::futures_io::AsyncWrite for HashingWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let writer = self.get_mut();
+        let poll = Pin::new(&mut writer.inner).poll_write(cx, buf);
+        hash_written(writer, buf, poll)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
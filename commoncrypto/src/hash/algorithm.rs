@@ -0,0 +1,134 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Descriptions of the digest algorithms supported by the current host.
+
+use commoncrypto_sys::{CCDigestGetBlockSize, CCHmacAlgorithm, CCPseudoRandomAlgorithm};
+use std::fmt;
+use std::str::FromStr;
+use {Error, Result};
+
+use super::{is_supported, CCDigestAlgorithm, Hasher};
+
+/// A digest algorithm which is supported on the current host, along with its sizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Algorithm {
+    algorithm: CCDigestAlgorithm,
+    block_len: usize,
+}
+
+impl Algorithm {
+    /// Describes the given digest `algorithm`.
+    ///
+    /// Returns `Error::Unimplemented` if the algorithm is not supported (see `is_supported`).
+    pub fn new(algorithm: CCDigestAlgorithm) -> Result<Algorithm> {
+        if !is_supported(algorithm) {
+            return Err(Error::Unimplemented);
+        }
+        Ok(Algorithm {
+            algorithm,
+            block_len: unsafe { CCDigestGetBlockSize(algorithm) },
+        })
+    }
+
+    /// The underlying `CommonCrypto` algorithm.
+    pub fn digest_algorithm(&self) -> CCDigestAlgorithm {
+        self.algorithm
+    }
+
+    /// Number of bytes in the digest output of the algorithm.
+    pub fn output_len(&self) -> usize {
+        self.algorithm.output_len()
+    }
+
+    /// Number of bytes in a block of input, as used by HMAC.
+    pub fn block_len(&self) -> usize {
+        self.block_len
+    }
+
+    /// The DER-encoded ASN.1 object identifier of the algorithm, if it has one.
+    pub fn oid(&self) -> Option<&'static [u8]> {
+        self.algorithm.oid()
+    }
+
+    /// Creates a new `Hasher` which uses the algorithm.
+    pub fn hasher(&self) -> Result<Hasher> {
+        Hasher::try_new(self.algorithm)
+    }
+
+    /// The equivalent algorithm for `hmac::Hmac`, if `CommonCrypto` provides one.
+    pub fn hmac_algorithm(&self) -> Option<CCHmacAlgorithm> {
+        match self.algorithm {
+            CCDigestAlgorithm::kCCDigestMD5 => Some(CCHmacAlgorithm::kCCHmacAlgMD5),
+            CCDigestAlgorithm::kCCDigestSHA1 => Some(CCHmacAlgorithm::kCCHmacAlgSHA1),
+            CCDigestAlgorithm::kCCDigestSHA224 => Some(CCHmacAlgorithm::kCCHmacAlgSHA224),
+            CCDigestAlgorithm::kCCDigestSHA256 => Some(CCHmacAlgorithm::kCCHmacAlgSHA256),
+            CCDigestAlgorithm::kCCDigestSHA384 => Some(CCHmacAlgorithm::kCCHmacAlgSHA384),
+            CCDigestAlgorithm::kCCDigestSHA512 => Some(CCHmacAlgorithm::kCCHmacAlgSHA512),
+            _ => None,
+        }
+    }
+
+    /// The equivalent pseudorandom function for `pbkdf2`, if `CommonCrypto` provides one.
+    pub fn prf(&self) -> Option<CCPseudoRandomAlgorithm> {
+        match self.algorithm {
+            CCDigestAlgorithm::kCCDigestSHA1 => Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1),
+            CCDigestAlgorithm::kCCDigestSHA224 => {
+                Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA224)
+            }
+            CCDigestAlgorithm::kCCDigestSHA256 => {
+                Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256)
+            }
+            CCDigestAlgorithm::kCCDigestSHA384 => {
+                Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA384)
+            }
+            CCDigestAlgorithm::kCCDigestSHA512 => {
+                Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA512)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.algorithm, f)
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    /// Parses an algorithm name, as accepted by `CCDigestAlgorithm`'s `FromStr`.
+    ///
+    /// Returns `Error::DecodeError` if the name is not recognized, and `Error::Unimplemented` if
+    /// the algorithm is not supported.
+    fn from_str(name: &str) -> Result<Algorithm> {
+        let algorithm = name
+            .parse::<CCDigestAlgorithm>()
+            .map_err(|_| Error::DecodeError)?;
+        Algorithm::new(algorithm)
+    }
+}
+
+/// Iterates over the digest algorithms which are supported on the current host.
+pub fn algorithms() -> impl Iterator<Item = Algorithm> {
+    CCDigestAlgorithm::all().filter_map(|algorithm| Algorithm::new(algorithm).ok())
+}
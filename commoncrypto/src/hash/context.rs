@@ -0,0 +1,174 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Hasher whose lifecycle is enforced by ownership.
+
+use super::{CCDigestAlgorithm, Digest, Hasher, State};
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+use {Error, Result};
+
+/// Generates cryptographic hashes, like `Hasher`, but cannot be used after it is finished.
+///
+/// The `finish` methods consume the context, so feeding data into a finished digest does not
+/// compile. To start over without finishing, call `reset`. Converting a `Hasher` into a
+/// `HashContext` discards any data fed into it after it was last finished.
+#[derive(Clone, Debug)]
+pub struct HashContext {
+    hasher: Hasher,
+}
+
+impl HashContext {
+    /// Creates a new `HashContext` which will use the given cryptographic `algorithm`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the algorithm is not supported. See `try_new`.
+    pub fn new(algorithm: CCDigestAlgorithm) -> HashContext {
+        HashContext {
+            hasher: Hasher::new(algorithm),
+        }
+    }
+
+    /// Creates a new `HashContext` which will use the given cryptographic `algorithm`, or returns
+    /// an error as `Hasher::try_new` does.
+    pub fn try_new(algorithm: CCDigestAlgorithm) -> Result<HashContext> {
+        Ok(HashContext {
+            hasher: Hasher::try_new(algorithm)?,
+        })
+    }
+
+    /// Feeds data into the digest.
+    pub fn update(&mut self, data: &[u8]) -> Result<()> {
+        self.hasher.update(data).map(|_| ())
+    }
+
+    /// Feeds data into the digest, and returns the context so that calls can be chained.
+    pub fn chain(mut self, data: &[u8]) -> Result<HashContext> {
+        self.update(data)?;
+        Ok(self)
+    }
+
+    /// Produces the digest output for the data fed in so far, without finishing the digest.
+    pub fn peek(&mut self) -> Result<Vec<u8>> {
+        self.hasher.peek()
+    }
+
+    /// Discards the data fed in so far, as if the context were newly created.
+    pub fn reset(&mut self) {
+        self.hasher.init();
+    }
+
+    /// Finishes the digest and produces the digest output.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        self.hasher.finish()
+    }
+
+    /// Finishes the digest and writes the digest output to the start of `out`. Returns the number
+    /// of bytes written.
+    ///
+    /// If `out` is shorter than the digest output, the digest is not finished, and the context is
+    /// returned inside the error with `Error::BufferTooSmall`.
+    pub fn finish_into(mut self, out: &mut [u8]) -> result::Result<usize, FinishError> {
+        match self.hasher.finish_into(out) {
+            Ok(len) => Ok(len),
+            Err(error) => Err(FinishError {
+                error,
+                context: self,
+            }),
+        }
+    }
+
+    /// Finishes the digest and produces the fixed-size digest output.
+    ///
+    /// `N` must be the output length of the algorithm, otherwise the digest is not finished, and
+    /// the context is returned inside the error with `Error::ParamError`.
+    pub fn finish_digest<const N: usize>(mut self) -> result::Result<Digest<N>, FinishError> {
+        match self.hasher.finish_digest() {
+            Ok(digest) => Ok(digest),
+            Err(error) => Err(FinishError {
+                error,
+                context: self,
+            }),
+        }
+    }
+}
+
+/// Error returned by `HashContext::finish_into` and `HashContext::finish_digest`, which holds the
+/// unfinished context so that it can be finished again.
+#[derive(Debug)]
+pub struct FinishError {
+    error: Error,
+    context: HashContext,
+}
+
+impl FinishError {
+    /// The reason the digest could not be finished.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// Returns the unfinished context.
+    pub fn into_context(self) -> HashContext {
+        self.context
+    }
+}
+
+impl fmt::Display for FinishError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl error::Error for FinishError {}
+
+impl From<FinishError> for Error {
+    fn from(err: FinishError) -> Error {
+        err.error
+    }
+}
+
+impl From<FinishError> for io::Error {
+    fn from(err: FinishError) -> io::Error {
+        err.error.into()
+    }
+}
+
+impl From<Hasher> for HashContext {
+    fn from(mut hasher: Hasher) -> HashContext {
+        if hasher.state == State::Finalized {
+            hasher.init();
+        }
+        HashContext { hasher }
+    }
+}
+
+impl io::Write for HashContext {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.hasher.update(buf)?)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Digest contexts for the `CC_*` hash functions, shared by the `digest` trait implementations
+//! and the resumable hashers.
+
+use super::CCDigestAlgorithm;
+use commoncrypto_sys::{
+    CC_MD5_Final, CC_MD5_Init, CC_MD5_Update, CC_SHA1_Final, CC_SHA1_Init, CC_SHA1_Update,
+    CC_SHA224_Final, CC_SHA224_Init, CC_SHA224_Update, CC_SHA256_Final, CC_SHA256_Init,
+    CC_SHA256_Update, CC_SHA384_Final, CC_SHA384_Init, CC_SHA384_Update, CC_SHA512_Final,
+    CC_SHA512_Init, CC_SHA512_Update, CC_LONG, CC_MD5_CTX, CC_SHA256_CTX, CC_SHA512_CTX,
+    CC_SHA_CTX, MD5_DIGEST_LENGTH, SHA1_DIGEST_LENGTH, SHA224_DIGEST_LENGTH, SHA256_DIGEST_LENGTH,
+    SHA384_DIGEST_LENGTH, SHA512_DIGEST_LENGTH,
+};
+use secret::wipe;
+use std::mem;
+use std::os::raw::c_uint;
+use std::slice;
+
+/// The bytes of a value which consists of integers only, in memory order.
+fn raw_bytes<T>(value: &T) -> &[u8] {
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, mem::size_of::<T>()) }
+}
+
+/// The bytes of a value which consists of integers only, which any bytes are valid for.
+fn raw_bytes_mut<T>(value: &mut T) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(value as *mut T as *mut u8, mem::size_of::<T>()) }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut word = [0; 4];
+    word.copy_from_slice(bytes);
+    u32::from_be_bytes(word)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut word = [0; 8];
+    word.copy_from_slice(bytes);
+    u64::from_be_bytes(word)
+}
+
+/// Hash state in the fields of a `CC_*_CTX`, as `CommonDigest.h` lays them out. The state is
+/// serialized as the number of bytes hashed, the chaining words and the partial block, with the
+/// integers big-endian.
+trait Fields {
+    /// Number of bytes hashed.
+    fn count(&self) -> u64;
+    /// Writes the state to `state`, whose length fits the count.
+    fn write_state(&self, state: &mut [u8]);
+    /// Sets the state from `state`, which has been validated by `HashState`.
+    fn read_state(&mut self, state: &[u8]);
+}
+
+/// MD5 and SHA-1 contexts count the bits hashed, and the bytes in the partial block.
+macro_rules! md_fields {
+    ($cc_ctx: ident, $($word: ident),+) => {
+        impl Fields for $cc_ctx {
+            fn count(&self) -> u64 {
+                (u64::from(self.Nh) << 32 | u64::from(self.Nl)) / 8
+            }
+
+            fn write_state(&self, state: &mut [u8]) {
+                let (count, mut rest) = state.split_at_mut(8);
+                count.copy_from_slice(&self.count().to_be_bytes());
+                $(
+                    let (word, next) = rest.split_at_mut(4);
+                    word.copy_from_slice(&self.$word.to_be_bytes());
+                    rest = next;
+                )+
+                rest.copy_from_slice(&raw_bytes(&self.data)[..rest.len()]);
+            }
+
+            fn read_state(&mut self, state: &[u8]) {
+                let count = read_u64(&state[..8]);
+                let mut rest = &state[8..];
+                $(
+                    self.$word = read_u32(&rest[..4]);
+                    rest = &rest[4..];
+                )+
+                let bits = count << 3;
+                self.Nl = bits as c_uint;
+                self.Nh = (bits >> 32) as c_uint;
+                self.data = Default::default();
+                raw_bytes_mut(&mut self.data)[..rest.len()].copy_from_slice(rest);
+                self.num = rest.len() as c_uint;
+            }
+        }
+    };
+}
+
+/// SHA-2 contexts count the bytes hashed, which determine the bytes in the partial block. Counts
+/// of 2^64 bytes or more are not supported.
+macro_rules! sha2_fields {
+    ($cc_ctx: ident, $word: ident, $read_word: ident) => {
+        impl Fields for $cc_ctx {
+            fn count(&self) -> u64 {
+                let word_bits = 8 * mem::size_of::<$word>();
+                (u128::from(self.count[0]) | u128::from(self.count[1]) << word_bits) as u64
+            }
+
+            fn write_state(&self, state: &mut [u8]) {
+                let word_len = mem::size_of::<$word>();
+                let (count, mut rest) = state.split_at_mut(8);
+                count.copy_from_slice(&self.count().to_be_bytes());
+                for hash in self.hash.iter() {
+                    let (word, next) = rest.split_at_mut(word_len);
+                    word.copy_from_slice(&hash.to_be_bytes());
+                    rest = next;
+                }
+                rest.copy_from_slice(&raw_bytes(&self.wbuf)[..rest.len()]);
+            }
+
+            fn read_state(&mut self, state: &[u8]) {
+                let word_len = mem::size_of::<$word>();
+                let count = read_u64(&state[..8]);
+                let mut rest = &state[8..];
+                for hash in self.hash.iter_mut() {
+                    *hash = $read_word(&rest[..word_len]);
+                    rest = &rest[word_len..];
+                }
+                self.count = [
+                    count as $word,
+                    (u128::from(count) >> (8 * word_len)) as $word,
+                ];
+                self.wbuf = Default::default();
+                raw_bytes_mut(&mut self.wbuf)[..rest.len()].copy_from_slice(rest);
+            }
+        }
+    };
+}
+
+md_fields!(CC_MD5_CTX, A, B, C, D);
+md_fields!(CC_SHA_CTX, h0, h1, h2, h3, h4);
+sha2_fields!(CC_SHA256_CTX, u32, read_u32);
+sha2_fields!(CC_SHA512_CTX, u64, read_u64);
+
+macro_rules! digest_context {
+    (
+        $name: ident,
+        $algorithm: ident,
+        $ctx_ty: ident,
+        $init_func: ident,
+        $update_func: ident,
+        $final_func: ident,
+        $len: ident
+    ) => {
+        /// Initialized digest context, which is wiped when it is dropped.
+        #[derive(Clone)]
+        pub struct $name {
+            ctx: $ctx_ty,
+        }
+
+        impl $name {
+            pub const ALGORITHM: CCDigestAlgorithm = CCDigestAlgorithm::$algorithm;
+            pub const LEN: usize = $len;
+
+            pub fn new() -> $name {
+                let mut ctx = $name {
+                    ctx: $ctx_ty::default(),
+                };
+                ctx.reset();
+                ctx
+            }
+
+            pub fn reset(&mut self) {
+                unsafe { $init_func(&mut self.ctx) };
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                for chunk in data.chunks(CC_LONG::MAX as usize) {
+                    unsafe { $update_func(&mut self.ctx, chunk.as_ptr(), chunk.len() as CC_LONG) };
+                }
+            }
+
+            /// Writes the digest to `md`, which must be `LEN` bytes long. The context must be
+            /// reset before it is updated again.
+            pub fn finish(&mut self, md: &mut [u8]) {
+                assert_eq!(md.len(), $name::LEN);
+                unsafe { $final_func(md.as_mut_ptr(), &mut self.ctx) };
+            }
+
+            /// Number of bytes hashed.
+            pub fn count(&self) -> u64 {
+                self.ctx.count()
+            }
+
+            /// Writes the serialized state to `state`, whose length fits `count`.
+            pub fn write_state(&self, state: &mut [u8]) {
+                self.ctx.write_state(state);
+            }
+
+            /// Sets the state from serialized state which has been validated by `HashState`.
+            pub fn read_state(&mut self, state: &[u8]) {
+                self.ctx.read_state(state);
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                wipe(raw_bytes_mut(&mut self.ctx));
+            }
+        }
+    };
+}
+
+digest_context!(
+    Md5Context,
+    kCCDigestMD5,
+    CC_MD5_CTX,
+    CC_MD5_Init,
+    CC_MD5_Update,
+    CC_MD5_Final,
+    MD5_DIGEST_LENGTH
+);
+digest_context!(
+    Sha1Context,
+    kCCDigestSHA1,
+    CC_SHA_CTX,
+    CC_SHA1_Init,
+    CC_SHA1_Update,
+    CC_SHA1_Final,
+    SHA1_DIGEST_LENGTH
+);
+digest_context!(
+    Sha224Context,
+    kCCDigestSHA224,
+    CC_SHA256_CTX,
+    CC_SHA224_Init,
+    CC_SHA224_Update,
+    CC_SHA224_Final,
+    SHA224_DIGEST_LENGTH
+);
+digest_context!(
+    Sha256Context,
+    kCCDigestSHA256,
+    CC_SHA256_CTX,
+    CC_SHA256_Init,
+    CC_SHA256_Update,
+    CC_SHA256_Final,
+    SHA256_DIGEST_LENGTH
+);
+digest_context!(
+    Sha384Context,
+    kCCDigestSHA384,
+    CC_SHA512_CTX,
+    CC_SHA384_Init,
+    CC_SHA384_Update,
+    CC_SHA384_Final,
+    SHA384_DIGEST_LENGTH
+);
+digest_context!(
+    Sha512Context,
+    kCCDigestSHA512,
+    CC_SHA512_CTX,
+    CC_SHA512_Init,
+    CC_SHA512_Update,
+    CC_SHA512_Final,
+    SHA512_DIGEST_LENGTH
+);
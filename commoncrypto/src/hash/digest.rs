@@ -0,0 +1,135 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Implementations of the `RustCrypto` `digest` traits, backed by the `CC_*` hash functions.
+
+use super::ctx::{
+    Md5Context, Sha1Context, Sha224Context, Sha256Context, Sha384Context, Sha512Context,
+};
+use digest::consts::{U128, U16, U20, U28, U32, U48, U64};
+use digest::core_api::BlockSizeUser;
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update};
+use std::fmt;
+
+macro_rules! impl_digest {
+    (
+        $(#[$attr: meta])*
+        $name: ident,
+        $ctx: ident,
+        $output_size: ty,
+        $block_size: ty
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name {
+            ctx: $ctx,
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name { ctx: $ctx::new() }
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($name)).finish()
+            }
+        }
+
+        impl HashMarker for $name {}
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $output_size;
+        }
+
+        impl BlockSizeUser for $name {
+            type BlockSize = $block_size;
+        }
+
+        impl Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                self.ctx.update(data);
+            }
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(mut self, out: &mut Output<Self>) {
+                self.ctx.finish(out);
+            }
+        }
+
+        impl FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                self.ctx.finish(out);
+                self.reset();
+            }
+        }
+
+        impl Reset for $name {
+            fn reset(&mut self) {
+                self.ctx.reset();
+            }
+        }
+    };
+}
+
+impl_digest!(
+    /// MD5 hasher.
+    Md5,
+    Md5Context,
+    U16,
+    U64
+);
+impl_digest!(
+    /// SHA1 hasher.
+    Sha1,
+    Sha1Context,
+    U20,
+    U64
+);
+impl_digest!(
+    /// SHA224 hasher.
+    Sha224,
+    Sha224Context,
+    U28,
+    U64
+);
+impl_digest!(
+    /// SHA256 hasher.
+    Sha256,
+    Sha256Context,
+    U32,
+    U64
+);
+impl_digest!(
+    /// SHA384 hasher.
+    Sha384,
+    Sha384Context,
+    U48,
+    U128
+);
+impl_digest!(
+    /// SHA512 hasher.
+    Sha512,
+    Sha512Context,
+    U64,
+    U128
+);
@@ -0,0 +1,56 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! DER encoding of the `DigestInfo` structure used by PKCS #1 v1.5 signatures.
+
+use super::CCDigestAlgorithm;
+use {Error, Result};
+
+const SEQUENCE: u8 = 0x30;
+const NULL: [u8; 2] = [0x05, 0x00];
+const OCTET_STRING: u8 = 0x04;
+
+/// Encodes `digest` as the DER `DigestInfo` structure of RFC 8017, section 9.2, which is signed
+/// by RSASSA-PKCS1-v1_5.
+///
+/// The algorithm parameters are encoded as `NULL`. Returns `Error::Unimplemented` if `algorithm`
+/// has no object identifier (see `CCDigestAlgorithm::oid`), and `Error::ParamError` if `digest` is
+/// not the output length of `algorithm`.
+pub fn digest_info(algorithm: CCDigestAlgorithm, digest: &[u8]) -> Result<Vec<u8>> {
+    let oid = algorithm.oid().ok_or(Error::Unimplemented)?;
+    if digest.len() != algorithm.output_len() {
+        return Err(Error::ParamError);
+    }
+    // Every length fits in the short form, which is a single byte below 0x80
+    let algorithm_len = oid.len() + NULL.len();
+    let digest_info_len = 2 + algorithm_len + 2 + digest.len();
+    let mut encoded = Vec::with_capacity(2 + digest_info_len);
+    encoded.extend_from_slice(&[
+        SEQUENCE,
+        digest_info_len as u8,
+        SEQUENCE,
+        algorithm_len as u8,
+    ]);
+    encoded.extend_from_slice(oid);
+    encoded.extend_from_slice(&NULL);
+    encoded.extend_from_slice(&[OCTET_STRING, digest.len() as u8]);
+    encoded.extend_from_slice(digest);
+    Ok(encoded)
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! One-shot hash functions, which hash a slice with the legacy `CC_*` functions.
+
+use super::resumable::{
+    ResumableMd5, ResumableSha1, ResumableSha224, ResumableSha256, ResumableSha384, ResumableSha512,
+};
+use commoncrypto_sys::{
+    CC_LONG, CC_MD5, CC_SHA1, CC_SHA224, CC_SHA256, CC_SHA384, CC_SHA512, MD5_DIGEST_LENGTH,
+    SHA1_DIGEST_LENGTH, SHA224_DIGEST_LENGTH, SHA256_DIGEST_LENGTH, SHA384_DIGEST_LENGTH,
+    SHA512_DIGEST_LENGTH,
+};
+
+macro_rules! oneshot {
+    ($name: ident, $func: ident, $resumable: ident, $len: ident, $doc: expr) => {
+        #[doc = $doc]
+        pub fn $name(data: &[u8]) -> [u8; $len] {
+            // The length is a CC_LONG, which is 32 bits wide
+            if data.len() > CC_LONG::MAX as usize {
+                let mut hasher = $resumable::new();
+                hasher.update(data);
+                return hasher.finish().into_bytes();
+            }
+            let mut md = [0; $len];
+            let result = unsafe { $func(data.as_ptr(), data.len() as CC_LONG, md.as_mut_ptr()) };
+            assert!(!result.is_null(), concat!(stringify!($func), " failed"));
+            md
+        }
+    };
+}
+
+oneshot!(
+    md5,
+    CC_MD5,
+    ResumableMd5,
+    MD5_DIGEST_LENGTH,
+    "Hashes `data` with MD5."
+);
+oneshot!(
+    sha1,
+    CC_SHA1,
+    ResumableSha1,
+    SHA1_DIGEST_LENGTH,
+    "Hashes `data` with SHA1."
+);
+oneshot!(
+    sha224,
+    CC_SHA224,
+    ResumableSha224,
+    SHA224_DIGEST_LENGTH,
+    "Hashes `data` with SHA224."
+);
+oneshot!(
+    sha256,
+    CC_SHA256,
+    ResumableSha256,
+    SHA256_DIGEST_LENGTH,
+    "Hashes `data` with SHA256."
+);
+oneshot!(
+    sha384,
+    CC_SHA384,
+    ResumableSha384,
+    SHA384_DIGEST_LENGTH,
+    "Hashes `data` with SHA384."
+);
+oneshot!(
+    sha512,
+    CC_SHA512,
+    ResumableSha512,
+    SHA512_DIGEST_LENGTH,
+    "Hashes `data` with SHA512."
+);
@@ -0,0 +1,152 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Fixed-size digest output.
+
+use commoncrypto_sys::{
+    MD5_DIGEST_LENGTH, SHA1_DIGEST_LENGTH, SHA224_DIGEST_LENGTH, SHA256_DIGEST_LENGTH,
+    SHA384_DIGEST_LENGTH, SHA512_DIGEST_LENGTH,
+};
+use constant_time;
+use encoding::{base64_decode, base64_encode};
+use std::fmt;
+use std::str::FromStr;
+use {Error, Result};
+
+/// Digest output of `N` bytes, such as that produced by `Hasher::finish_digest`.
+///
+/// Comparisons between digests take constant time. `Display` and `FromStr` use lowercase
+/// hexadecimal.
+#[derive(Clone, Copy)]
+pub struct Digest<const N: usize>([u8; N]);
+
+/// Digest output of MD5.
+pub type Md5Digest = Digest<MD5_DIGEST_LENGTH>;
+/// Digest output of SHA1.
+pub type Sha1Digest = Digest<SHA1_DIGEST_LENGTH>;
+/// Digest output of SHA224.
+pub type Sha224Digest = Digest<SHA224_DIGEST_LENGTH>;
+/// Digest output of SHA256.
+pub type Sha256Digest = Digest<SHA256_DIGEST_LENGTH>;
+/// Digest output of SHA384.
+pub type Sha384Digest = Digest<SHA384_DIGEST_LENGTH>;
+/// Digest output of SHA512.
+pub type Sha512Digest = Digest<SHA512_DIGEST_LENGTH>;
+
+impl<const N: usize> Digest<N> {
+    /// Number of bytes in the digest.
+    pub const LEN: usize = N;
+
+    /// Returns the digest as a byte array.
+    pub fn into_bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    /// Encodes the digest as padded, standard base64.
+    pub fn to_base64(&self) -> String {
+        base64_encode(&self.0, true)
+    }
+
+    /// Decodes a digest from padded, standard base64.
+    pub fn from_base64(encoded: &str) -> Result<Digest<N>> {
+        let decoded = base64_decode(encoded, true)?;
+        if decoded.len() != N {
+            return Err(Error::DecodeError);
+        }
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(&decoded);
+        Ok(Digest(bytes))
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Digest<N> {
+    fn from(bytes: [u8; N]) -> Digest<N> {
+        Digest(bytes)
+    }
+}
+
+impl<const N: usize> From<Digest<N>> for [u8; N] {
+    fn from(digest: Digest<N>) -> [u8; N] {
+        digest.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Digest<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq for Digest<N> {
+    fn eq(&self, other: &Digest<N>) -> bool {
+        constant_time::eq(&self.0, &other.0)
+    }
+}
+
+impl<const N: usize> Eq for Digest<N> {}
+
+impl<const N: usize> fmt::LowerHex for Digest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::UpperHex for Digest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Display for Digest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for Digest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Digest<{}>({:x})", N, self)
+    }
+}
+
+impl<const N: usize> FromStr for Digest<N> {
+    type Err = Error;
+
+    /// Decodes a digest from hexadecimal, in either case.
+    fn from_str(encoded: &str) -> Result<Digest<N>> {
+        let encoded = encoded.as_bytes();
+        if encoded.len() != N * 2 {
+            return Err(Error::DecodeError);
+        }
+        let mut bytes = [0u8; N];
+        for (byte, pair) in bytes.iter_mut().zip(encoded.chunks(2)) {
+            let high = (pair[0] as char).to_digit(16).ok_or(Error::DecodeError)?;
+            let low = (pair[1] as char).to_digit(16).ok_or(Error::DecodeError)?;
+            *byte = (high << 4 | low) as u8;
+        }
+        Ok(Digest(bytes))
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Pool of hashers which recycles their digest contexts.
+
+use super::{CCDigestAlgorithm, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use Result;
+
+/// Thread-safe pool of `Hasher`s for a single algorithm.
+///
+/// Hashers are reset with `CCDigestReset` when they are returned to the pool, so that hashing a
+/// large number of small inputs does not allocate a digest context for each one. Clones of a pool
+/// share its idle hashers.
+#[derive(Clone, Debug)]
+pub struct HasherPool {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    algorithm: CCDigestAlgorithm,
+    max_idle: usize,
+    idle: Mutex<Vec<Hasher>>,
+}
+
+impl HasherPool {
+    /// Creates a new pool of hashers which use the given `algorithm`, and which keeps at most
+    /// `max_idle` hashers that are not in use.
+    pub fn new(algorithm: CCDigestAlgorithm, max_idle: usize) -> HasherPool {
+        HasherPool {
+            shared: Arc::new(Shared {
+                algorithm,
+                max_idle,
+                idle: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// The algorithm used by the hashers in the pool.
+    pub fn algorithm(&self) -> CCDigestAlgorithm {
+        self.shared.algorithm
+    }
+
+    /// Number of hashers in the pool which are not in use.
+    pub fn idle(&self) -> usize {
+        self.shared.lock().len()
+    }
+
+    /// Takes a reset hasher from the pool, creating one with `Hasher::try_new` if there are none
+    /// idle. The hasher is returned to the pool when it is dropped.
+    ///
+    /// The pooled hasher keeps the pool's idle hashers alive, so it can outlive `self` and be
+    /// moved to another thread.
+    pub fn get(&self) -> Result<PooledHasher> {
+        let hasher = match self.shared.lock().pop() {
+            Some(hasher) => hasher,
+            None => Hasher::try_new(self.shared.algorithm)?,
+        };
+        Ok(PooledHasher {
+            pool: self.shared.clone(),
+            hasher: Some(hasher),
+        })
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Vec<Hasher>> {
+        // A panic cannot leave the list of idle hashers inconsistent
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn put(&self, mut hasher: Hasher) {
+        hasher.init();
+        let mut idle = self.lock();
+        if idle.len() < self.max_idle {
+            idle.push(hasher);
+        }
+    }
+}
+
+/// Hasher taken from a `HasherPool`, which is reset and returned to the pool when it is dropped.
+#[derive(Debug)]
+pub struct PooledHasher {
+    pool: Arc<Shared>,
+    hasher: Option<Hasher>,
+}
+
+impl Deref for PooledHasher {
+    type Target = Hasher;
+
+    fn deref(&self) -> &Hasher {
+        self.hasher.as_ref().expect("hasher already returned")
+    }
+}
+
+impl DerefMut for PooledHasher {
+    fn deref_mut(&mut self) -> &mut Hasher {
+        self.hasher.as_mut().expect("hasher already returned")
+    }
+}
+
+impl Drop for PooledHasher {
+    fn drop(&mut self) {
+        if let Some(hasher) = self.hasher.take() {
+            self.pool.put(hasher);
+        }
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Hashers whose intermediate state can be exported and restored later.
+
+use super::ctx::{
+    Md5Context, Sha1Context, Sha224Context, Sha256Context, Sha384Context, Sha512Context,
+};
+use super::output::{
+    Md5Digest, Sha1Digest, Sha224Digest, Sha256Digest, Sha384Digest, Sha512Digest,
+};
+use super::{CCDigestAlgorithm, Digest};
+use secret::SecretBytes;
+use std::fmt;
+use std::io;
+use {Error, Result};
+
+const MAGIC: &[u8; 4] = b"CCHS";
+/// The magic bytes, the format version and the algorithm.
+const HEADER_LEN: usize = 6;
+/// The number of bytes hashed, which precedes the chaining words.
+const COUNT_LEN: usize = 8;
+
+/// Sizes of the hash state of an algorithm.
+struct Layout {
+    /// Bytes in the chaining words
+    words_len: usize,
+    block_len: usize,
+    /// Largest number of bytes hashed which the digest context can count
+    max_count: u64,
+}
+
+impl Layout {
+    fn new(algorithm: CCDigestAlgorithm) -> Option<Layout> {
+        let (words_len, block_len, max_count) = match algorithm {
+            // The MD5 and SHA-1 contexts count bits in 64 bits
+            CCDigestAlgorithm::kCCDigestMD5 => (16, 64, u64::MAX >> 3),
+            CCDigestAlgorithm::kCCDigestSHA1 => (20, 64, u64::MAX >> 3),
+            CCDigestAlgorithm::kCCDigestSHA224 | CCDigestAlgorithm::kCCDigestSHA256 => {
+                (32, 64, u64::MAX)
+            }
+            CCDigestAlgorithm::kCCDigestSHA384 | CCDigestAlgorithm::kCCDigestSHA512 => {
+                (64, 128, u64::MAX)
+            }
+            _ => return None,
+        };
+        Some(Layout {
+            words_len,
+            block_len,
+            max_count,
+        })
+    }
+
+    /// Length of the state after `count` bytes have been hashed.
+    fn state_len(&self, count: u64) -> usize {
+        COUNT_LEN + self.words_len + (count % self.block_len as u64) as usize
+    }
+}
+
+fn algorithm_from_id(id: u8) -> Option<CCDigestAlgorithm> {
+    [
+        CCDigestAlgorithm::kCCDigestMD5,
+        CCDigestAlgorithm::kCCDigestSHA1,
+        CCDigestAlgorithm::kCCDigestSHA224,
+        CCDigestAlgorithm::kCCDigestSHA256,
+        CCDigestAlgorithm::kCCDigestSHA384,
+        CCDigestAlgorithm::kCCDigestSHA512,
+    ]
+    .iter()
+    .find(|algorithm| **algorithm as u8 == id)
+    .cloned()
+}
+
+/// Intermediate state of a resumable hasher, in a versioned byte format.
+///
+/// The format is a 6-byte header, consisting of the magic bytes `CCHS`, the format version and
+/// the algorithm, followed by the hash state: the number of bytes hashed as a 64-bit integer, the
+/// chaining words, and the bytes of the partial block, with the integers big-endian. The state is
+/// read from the fields of the digest context, as `CommonDigest.h` lays them out, so it can be
+/// restored on any host, whether it uses Apple's `CommonCrypto` or `commoncrypto-shim`.
+///
+/// The state includes data which has not yet been hashed, so it is wiped when it is dropped, and
+/// it is not shown by `Debug`. With the `serde` feature, it is serialized as bytes.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HashState {
+    algorithm: CCDigestAlgorithm,
+    bytes: SecretBytes,
+}
+
+impl HashState {
+    /// Version of the format produced by `as_bytes`.
+    pub const VERSION: u8 = 1;
+
+    fn new(
+        algorithm: CCDigestAlgorithm,
+        count: u64,
+        write_state: impl FnOnce(&mut [u8]),
+    ) -> HashState {
+        let layout = Layout::new(algorithm).expect("no resumable hasher for the algorithm");
+        let mut bytes = SecretBytes::new(HEADER_LEN + layout.state_len(count));
+        bytes[..4].copy_from_slice(MAGIC);
+        bytes[4] = HashState::VERSION;
+        bytes[5] = algorithm as u8;
+        write_state(&mut bytes[HEADER_LEN..]);
+        HashState { algorithm, bytes }
+    }
+
+    /// Parses state in the format produced by `as_bytes`.
+    ///
+    /// Returns `Error::DecodeError` if the header is malformed, the version is not supported, the
+    /// number of bytes hashed is too large for the algorithm, or the length of the state does not
+    /// match the algorithm and the number of bytes hashed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<HashState> {
+        if bytes.len() < HEADER_LEN + COUNT_LEN
+            || &bytes[..4] != MAGIC
+            || bytes[4] != HashState::VERSION
+        {
+            return Err(Error::DecodeError);
+        }
+        let algorithm = algorithm_from_id(bytes[5]).ok_or(Error::DecodeError)?;
+        let layout = Layout::new(algorithm).ok_or(Error::DecodeError)?;
+        let mut count = [0; COUNT_LEN];
+        count.copy_from_slice(&bytes[HEADER_LEN..HEADER_LEN + COUNT_LEN]);
+        let count = u64::from_be_bytes(count);
+        if count > layout.max_count || bytes.len() != HEADER_LEN + layout.state_len(count) {
+            return Err(Error::DecodeError);
+        }
+        Ok(HashState {
+            algorithm,
+            bytes: SecretBytes::from_slice(bytes),
+        })
+    }
+
+    /// The digest algorithm of the hasher which exported the state.
+    pub fn algorithm(&self) -> CCDigestAlgorithm {
+        self.algorithm
+    }
+
+    /// The state in its versioned byte format.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The hash state, if it was exported by a hasher for `algorithm`.
+    fn state(&self, algorithm: CCDigestAlgorithm) -> Result<&[u8]> {
+        if self.algorithm != algorithm {
+            return Err(Error::ParamError);
+        }
+        Ok(&self.bytes[HEADER_LEN..])
+    }
+}
+
+impl fmt::Debug for HashState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HashState")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for HashState {
+    fn serialize<S: ::serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> ::std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.as_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for HashState {
+    fn deserialize<D: ::serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> ::std::result::Result<HashState, D::Error> {
+        struct StateVisitor;
+
+        impl<'de> ::serde::de::Visitor<'de> for StateVisitor {
+            type Value = HashState;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("resumable hash state bytes")
+            }
+
+            fn visit_bytes<E: ::serde::de::Error>(
+                self,
+                bytes: &[u8],
+            ) -> ::std::result::Result<HashState, E> {
+                HashState::from_bytes(bytes).map_err(E::custom)
+            }
+
+            fn visit_seq<A: ::serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> ::std::result::Result<HashState, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                let bytes = SecretBytes::from(bytes);
+                HashState::from_bytes(&bytes).map_err(::serde::de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_bytes(StateVisitor)
+    }
+}
+
+macro_rules! resumable_hasher {
+    ($name: ident, $ctx: ident, $digest: ident, $doc: expr) => {
+        #[doc = $doc]
+        ///
+        /// Its state can be exported with `export` at any point, and restored with `resume`.
+        #[derive(Clone)]
+        pub struct $name {
+            ctx: $ctx,
+        }
+
+        impl $name {
+            /// The algorithm of the hasher.
+            pub const ALGORITHM: CCDigestAlgorithm = $ctx::ALGORITHM;
+            /// The length of the digest output, in bytes.
+            pub const LEN: usize = $digest::LEN;
+
+            /// Creates a new hasher.
+            pub fn new() -> $name {
+                $name { ctx: $ctx::new() }
+            }
+
+            /// Feeds data into the hasher.
+            pub fn update(&mut self, data: &[u8]) {
+                self.ctx.update(data);
+            }
+
+            /// Finalizes digest operations and produces the digest output.
+            pub fn finish(mut self) -> $digest {
+                let mut md = [0; $digest::LEN];
+                self.ctx.finish(&mut md);
+                Digest::from(md)
+            }
+
+            /// Exports the intermediate state of the hasher.
+            pub fn export(&self) -> HashState {
+                HashState::new($ctx::ALGORITHM, self.ctx.count(), |state| {
+                    self.ctx.write_state(state)
+                })
+            }
+
+            /// Restores a hasher from state exported by `export`.
+            ///
+            /// Returns `Error::ParamError` if the state was exported by a hasher for another
+            /// algorithm.
+            ///
+            /// The state is only validated for its format, so state which has been tampered with
+            /// produces a wrong digest. Store it where it cannot be modified by others, or
+            /// authenticate it, for example with `hmac::Hmac`.
+            pub fn resume(state: &HashState) -> Result<$name> {
+                let state = state.state($ctx::ALGORITHM)?;
+                let mut ctx = $ctx::new();
+                ctx.read_state(state);
+                Ok($name { ctx })
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name::new()
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($name)).finish_non_exhaustive()
+            }
+        }
+
+        impl io::Write for $name {
+            #[inline]
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.update(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+resumable_hasher!(
+    ResumableMd5,
+    Md5Context,
+    Md5Digest,
+    "MD5 hasher which can be resumed later."
+);
+resumable_hasher!(
+    ResumableSha1,
+    Sha1Context,
+    Sha1Digest,
+    "SHA1 hasher which can be resumed later."
+);
+resumable_hasher!(
+    ResumableSha224,
+    Sha224Context,
+    Sha224Digest,
+    "SHA224 hasher which can be resumed later."
+);
+resumable_hasher!(
+    ResumableSha256,
+    Sha256Context,
+    Sha256Digest,
+    "SHA256 hasher which can be resumed later."
+);
+resumable_hasher!(
+    ResumableSha384,
+    Sha384Context,
+    Sha384Digest,
+    "SHA384 hasher which can be resumed later."
+);
+resumable_hasher!(
+    ResumableSha512,
+    Sha512Context,
+    Sha512Digest,
+    "SHA512 hasher which can be resumed later."
+);
@@ -0,0 +1,138 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Idiomatic Rust wrapper for `CommonCrypto`'s `CCHmacContext` struct.
+
+use commoncrypto_sys::{
+    CCHmac, CCHmacContext, CCHmacFinal, CCHmacInit, CCHmacUpdate, MD5_DIGEST_LENGTH,
+    SHA1_DIGEST_LENGTH, SHA224_DIGEST_LENGTH, SHA256_DIGEST_LENGTH, SHA384_DIGEST_LENGTH,
+    SHA512_DIGEST_LENGTH,
+};
+use constant_time;
+use secret::wipe;
+use std::fmt;
+use std::io;
+use std::mem;
+use std::slice;
+
+pub use commoncrypto_sys::CCHmacAlgorithm;
+
+fn output_size(algorithm: CCHmacAlgorithm) -> usize {
+    match algorithm {
+        CCHmacAlgorithm::kCCHmacAlgSHA1 => SHA1_DIGEST_LENGTH,
+        CCHmacAlgorithm::kCCHmacAlgMD5 => MD5_DIGEST_LENGTH,
+        CCHmacAlgorithm::kCCHmacAlgSHA256 => SHA256_DIGEST_LENGTH,
+        CCHmacAlgorithm::kCCHmacAlgSHA384 => SHA384_DIGEST_LENGTH,
+        CCHmacAlgorithm::kCCHmacAlgSHA512 => SHA512_DIGEST_LENGTH,
+        CCHmacAlgorithm::kCCHmacAlgSHA224 => SHA224_DIGEST_LENGTH,
+    }
+}
+
+/// Generates keyed-hash message authentication codes.
+///
+/// The contexts, which are derived from the key, are wiped when the `Hmac` is dropped.
+pub struct Hmac {
+    ctx: CCHmacContext,
+    initial_ctx: CCHmacContext,
+    algorithm: CCHmacAlgorithm,
+}
+
+impl Hmac {
+    /// Creates a new `Hmac` which will use the given `algorithm` and `key`.
+    pub fn new(algorithm: CCHmacAlgorithm, key: &[u8]) -> Hmac {
+        let mut ctx = CCHmacContext::default();
+        unsafe { CCHmacInit(&mut ctx, algorithm, key.as_ptr(), key.len()) };
+        Hmac {
+            initial_ctx: ctx.clone(),
+            ctx,
+            algorithm,
+        }
+    }
+
+    /// Feeds data into the HMAC.
+    pub fn update(&mut self, data: &[u8]) {
+        unsafe { CCHmacUpdate(&mut self.ctx, data.as_ptr(), data.len()) };
+    }
+
+    /// Produces the message authentication code for the data fed in so far, then resets the
+    /// `Hmac` so that it can be reused with the same key.
+    pub fn finish(&mut self) -> Vec<u8> {
+        let mut mac = vec![0; output_size(self.algorithm)];
+        unsafe { CCHmacFinal(&mut self.ctx, mac.as_mut_ptr()) };
+        self.ctx = self.initial_ctx.clone();
+        mac
+    }
+
+    /// Checks, in constant time, whether `tag` is the message authentication code for the data
+    /// fed in so far. Resets the `Hmac` like `finish`.
+    pub fn verify(&mut self, tag: &[u8]) -> bool {
+        constant_time::eq(&self.finish(), tag)
+    }
+}
+
+impl fmt::Debug for Hmac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Hmac")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Hmac {
+    fn drop(&mut self) {
+        for ctx in [&mut self.ctx, &mut self.initial_ctx].iter_mut() {
+            let bytes = unsafe {
+                slice::from_raw_parts_mut(
+                    &mut **ctx as *mut CCHmacContext as *mut u8,
+                    mem::size_of::<CCHmacContext>(),
+                )
+            };
+            wipe(bytes);
+        }
+    }
+}
+
+impl io::Write for Hmac {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Generates the message authentication code for `data` in one call.
+pub fn hmac(algorithm: CCHmacAlgorithm, key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = vec![0; output_size(algorithm)];
+    unsafe {
+        CCHmac(
+            algorithm,
+            key.as_ptr(),
+            key.len(),
+            data.as_ptr(),
+            data.len(),
+            mac.as_mut_ptr(),
+        )
+    };
+    mac
+}
@@ -22,9 +22,43 @@
 
 #![warn(missing_docs)]
 
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
 extern crate commoncrypto_sys;
+#[cfg(feature = "digest")]
+extern crate digest;
+#[cfg(feature = "futures-io")]
+extern crate futures_io;
+#[cfg(feature = "password-hash")]
+extern crate password_hash;
+#[cfg(feature = "rand_core")]
+extern crate rand_core;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
 
+mod constant_time;
+mod encoding;
+mod error;
+
+#[warn(missing_docs)]
+pub mod aead;
+#[warn(missing_docs)]
+pub mod cryptor;
 #[warn(missing_docs)]
 pub mod hash;
 #[warn(missing_docs)]
+pub mod hmac;
+#[warn(missing_docs)]
+pub mod otp;
+#[warn(missing_docs)]
 pub mod pbkdf2;
+#[warn(missing_docs)]
+pub mod random;
+#[warn(missing_docs)]
+pub mod secret;
+
+pub use error::{Error, Result};
@@ -0,0 +1,233 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! One-time passwords: HOTP (RFC 4226) and TOTP (RFC 6238), built on `hmac`.
+//!
+//! The secrets and parameters of both kinds can be exchanged with authenticator apps as
+//! `otpauth://` URIs, via `OtpAuthUri`.
+
+use constant_time;
+use hmac::{hmac, CCHmacAlgorithm};
+use secret::{wipe, SecretBytes};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use {Error, Result};
+
+mod uri;
+
+pub use self::uri::{Otp, OtpAuthUri};
+
+/// Generates and verifies HMAC-based one-time passwords (HOTP), as specified by RFC 4226.
+///
+/// The secret is wiped when the `Hotp` is dropped, and is not shown by `Debug`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hotp {
+    algorithm: CCHmacAlgorithm,
+    secret: SecretBytes,
+    digits: u32,
+}
+
+impl Hotp {
+    /// Number of digits in a password, unless otherwise specified.
+    pub const DEFAULT_DIGITS: u32 = 6;
+    /// Largest number of counters which `verify` looks ahead, as each one costs an HMAC.
+    pub const MAX_LOOK_AHEAD: u64 = 100;
+    /// Shortest secret accepted, in bytes. RFC 4226 requires at least 128 bits.
+    pub const MIN_SECRET_LEN: usize = 16;
+
+    /// Creates a new `Hotp` which generates passwords of `digits` digits from `secret` with
+    /// HMAC-`algorithm`.
+    ///
+    /// Returns `Error::ParamError` unless `algorithm` is SHA-1, SHA-256 or SHA-512, `secret` is
+    /// at least `MIN_SECRET_LEN` bytes long and `digits` is between 6 and 10.
+    pub fn new(algorithm: CCHmacAlgorithm, secret: &[u8], digits: u32) -> Result<Hotp> {
+        match algorithm {
+            CCHmacAlgorithm::kCCHmacAlgSHA1
+            | CCHmacAlgorithm::kCCHmacAlgSHA256
+            | CCHmacAlgorithm::kCCHmacAlgSHA512 => {}
+            _ => return Err(Error::ParamError),
+        }
+        if secret.len() < Hotp::MIN_SECRET_LEN || !(6..=10).contains(&digits) {
+            return Err(Error::ParamError);
+        }
+        Ok(Hotp {
+            algorithm,
+            secret: SecretBytes::from_slice(secret),
+            digits,
+        })
+    }
+
+    /// The HMAC algorithm.
+    pub fn algorithm(&self) -> CCHmacAlgorithm {
+        self.algorithm
+    }
+
+    /// The shared secret.
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// Number of digits in a password.
+    pub fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// Generates the password for `counter`.
+    pub fn generate(&self, counter: u64) -> String {
+        let mut mac = hmac(self.algorithm, &self.secret, &counter.to_be_bytes());
+        // Dynamic truncation, RFC 4226 section 5.3
+        let offset = (mac[mac.len() - 1] & 0xf) as usize;
+        let code = u32::from_be_bytes([
+            mac[offset] & 0x7f,
+            mac[offset + 1],
+            mac[offset + 2],
+            mac[offset + 3],
+        ]);
+        wipe(&mut mac);
+        format!(
+            "{:0width$}",
+            u64::from(code) % 10u64.pow(self.digits),
+            width = self.digits as usize
+        )
+    }
+
+    /// Checks `code` against the passwords for `counter` and the `look_ahead` counters after it.
+    /// Returns the counter whose password matched, if any.
+    ///
+    /// The counter stored for the client should be set to one past the returned counter, so that
+    /// the password cannot be reused.
+    ///
+    /// Returns `Error::ParamError` if `look_ahead` is greater than `MAX_LOOK_AHEAD`.
+    pub fn verify(&self, code: &str, counter: u64, look_ahead: u64) -> Result<Option<u64>> {
+        if look_ahead > Hotp::MAX_LOOK_AHEAD {
+            return Err(Error::ParamError);
+        }
+        Ok(self.find(code, counter, look_ahead))
+    }
+
+    fn find(&self, code: &str, counter: u64, look_ahead: u64) -> Option<u64> {
+        (counter..=counter.saturating_add(look_ahead))
+            .find(|counter| constant_time::eq(code.as_bytes(), self.generate(*counter).as_bytes()))
+    }
+}
+
+/// Generates and verifies time-based one-time passwords (TOTP), as specified by RFC 6238.
+///
+/// The time steps are counted from the Unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Totp {
+    hotp: Hotp,
+    step: u64,
+    skew: u64,
+}
+
+impl Totp {
+    /// Length of a time step, unless otherwise specified.
+    pub const DEFAULT_STEP: Duration = Duration::from_secs(30);
+    /// Largest skew, which keeps the window within `Hotp::MAX_LOOK_AHEAD`.
+    pub const MAX_SKEW: u64 = Hotp::MAX_LOOK_AHEAD / 2;
+
+    /// Creates a new `Totp` which generates passwords of `digits` digits from `secret` with
+    /// HMAC-`algorithm`, in steps of 30 seconds, accepting passwords from one step either side of
+    /// the current one.
+    ///
+    /// Returns `Error::ParamError` under the same conditions as `Hotp::new`.
+    pub fn new(algorithm: CCHmacAlgorithm, secret: &[u8], digits: u32) -> Result<Totp> {
+        Ok(Totp {
+            hotp: Hotp::new(algorithm, secret, digits)?,
+            step: Totp::DEFAULT_STEP.as_secs(),
+            skew: 1,
+        })
+    }
+
+    /// Sets the length of a time step.
+    ///
+    /// Returns `Error::ParamError` unless `step` is a whole, nonzero number of seconds.
+    pub fn with_step(mut self, step: Duration) -> Result<Totp> {
+        if step.as_secs() == 0 || step.subsec_nanos() != 0 {
+            return Err(Error::ParamError);
+        }
+        self.step = step.as_secs();
+        Ok(self)
+    }
+
+    /// Sets the number of time steps either side of the current one whose passwords are accepted,
+    /// to allow for clock drift and delays in entering the password.
+    ///
+    /// Returns `Error::ParamError` if `skew` is greater than `MAX_SKEW`.
+    pub fn with_skew(mut self, skew: u64) -> Result<Totp> {
+        if skew > Totp::MAX_SKEW {
+            return Err(Error::ParamError);
+        }
+        self.skew = skew;
+        Ok(self)
+    }
+
+    /// The underlying HOTP parameters.
+    pub fn hotp(&self) -> &Hotp {
+        &self.hotp
+    }
+
+    /// Length of a time step.
+    pub fn step(&self) -> Duration {
+        Duration::from_secs(self.step)
+    }
+
+    /// Number of time steps either side of the current one whose passwords are accepted.
+    pub fn skew(&self) -> u64 {
+        self.skew
+    }
+
+    /// The time step which `time` falls in.
+    ///
+    /// Returns `Error::ParamError` if `time` is before the Unix epoch.
+    pub fn time_step(&self, time: SystemTime) -> Result<u64> {
+        let elapsed = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::ParamError)?;
+        Ok(elapsed.as_secs() / self.step)
+    }
+
+    /// Generates the password for the current time.
+    pub fn generate(&self) -> Result<String> {
+        self.generate_at(SystemTime::now())
+    }
+
+    /// Generates the password for `time`.
+    pub fn generate_at(&self, time: SystemTime) -> Result<String> {
+        Ok(self.hotp.generate(self.time_step(time)?))
+    }
+
+    /// Checks `code` against the passwords for the current time, within the skew window. Returns
+    /// the time step whose password matched, if any.
+    ///
+    /// To stop a password from being reused, the returned time step should be recorded, and
+    /// passwords from it and earlier steps rejected.
+    pub fn verify(&self, code: &str) -> Option<u64> {
+        self.verify_at(code, SystemTime::now())
+    }
+
+    /// Checks `code` against the passwords for `time`, within the skew window. See `verify`.
+    pub fn verify_at(&self, code: &str, time: SystemTime) -> Option<u64> {
+        let step = self.time_step(time).ok()?;
+        let first = step.saturating_sub(self.skew);
+        let look_ahead = step.saturating_add(self.skew) - first;
+        self.hotp.find(code, first, look_ahead)
+    }
+}
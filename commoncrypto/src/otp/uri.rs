@@ -0,0 +1,218 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! `otpauth://` URIs, in the format used by authenticator apps, such as
+//! `otpauth://totp/Example:alice@example.com?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=Example`.
+//!
+//! The secret is encoded in unpadded Base32 (RFC 4648), and the label and issuer are
+//! percent-encoded.
+
+use super::{Hotp, Totp};
+use encoding::{base32_decode, base32_encode};
+use hash::Algorithm;
+use hmac::CCHmacAlgorithm;
+use secret::SecretBytes;
+use std::time::Duration;
+use {Error, Result};
+
+/// A one-time password generator described by an `otpauth://` URI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Otp {
+    /// HOTP, with the counter of the next password
+    Hotp(Hotp, u64),
+    /// TOTP
+    Totp(Totp),
+}
+
+impl Otp {
+    fn hotp(&self) -> &Hotp {
+        match *self {
+            Otp::Hotp(ref hotp, _) => hotp,
+            Otp::Totp(ref totp) => totp.hotp(),
+        }
+    }
+}
+
+/// The contents of an `otpauth://` URI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OtpAuthUri {
+    /// The password generator, including its secret
+    pub otp: Otp,
+    /// The account name, optionally prefixed with the issuer and a colon
+    pub label: String,
+    /// The provider or service which the account belongs to
+    pub issuer: Option<String>,
+}
+
+impl OtpAuthUri {
+    /// Parses an `otpauth://` URI.
+    ///
+    /// Returns `Error::DecodeError` if the URI is malformed or has no secret, `Error::Unimplemented`
+    /// if its algorithm is not supported, and `Error::ParamError` if its parameters are invalid for
+    /// `Hotp::new` or `Totp::with_step`. Unknown parameters are ignored.
+    pub fn parse(uri: &str) -> Result<OtpAuthUri> {
+        let scheme_len = "otpauth://".len();
+        // Not sliced by index, as the URI may have a multibyte character across the boundary
+        let scheme = uri.get(..scheme_len).ok_or(Error::DecodeError)?;
+        if !scheme.eq_ignore_ascii_case("otpauth://") {
+            return Err(Error::DecodeError);
+        }
+        let rest = &uri[scheme_len..];
+        let (kind, rest) = rest.split_at(rest.find('/').ok_or(Error::DecodeError)?);
+        let (label, query) = match rest[1..].find('?') {
+            Some(i) => (&rest[1..=i], &rest[i + 2..]),
+            None => (&rest[1..], ""),
+        };
+
+        let mut secret = None;
+        let mut issuer = None;
+        let mut algorithm = CCHmacAlgorithm::kCCHmacAlgSHA1;
+        let mut digits = Hotp::DEFAULT_DIGITS;
+        let mut period = Totp::DEFAULT_STEP.as_secs();
+        let mut counter = None;
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (name, value) = match param.find('=') {
+                Some(i) => (&param[..i], percent_decode(&param[i + 1..], true)?),
+                None => (param, String::new()),
+            };
+            match name {
+                "secret" => secret = Some(decode_secret(&value)?),
+                "issuer" => issuer = Some(value),
+                "algorithm" => {
+                    algorithm = value
+                        .parse::<Algorithm>()?
+                        .hmac_algorithm()
+                        .ok_or(Error::Unimplemented)?
+                }
+                "digits" => digits = value.parse().map_err(|_| Error::DecodeError)?,
+                "period" => period = value.parse().map_err(|_| Error::DecodeError)?,
+                "counter" => counter = Some(value.parse().map_err(|_| Error::DecodeError)?),
+                _ => {}
+            }
+        }
+
+        let secret = secret.ok_or(Error::DecodeError)?;
+        let otp = if kind.eq_ignore_ascii_case("hotp") {
+            let counter = counter.ok_or(Error::DecodeError)?;
+            Otp::Hotp(Hotp::new(algorithm, &secret, digits)?, counter)
+        } else if kind.eq_ignore_ascii_case("totp") {
+            Otp::Totp(
+                Totp::new(algorithm, &secret, digits)?.with_step(Duration::from_secs(period))?,
+            )
+        } else {
+            return Err(Error::DecodeError);
+        };
+        Ok(OtpAuthUri {
+            otp,
+            label: percent_decode(label, false)?,
+            issuer,
+        })
+    }
+
+    /// Encodes the URI, including the secret.
+    pub fn to_uri(&self) -> String {
+        let hotp = self.otp.hotp();
+        let kind = match self.otp {
+            Otp::Hotp(..) => "hotp",
+            Otp::Totp(_) => "totp",
+        };
+        let mut uri = format!(
+            "otpauth://{}/{}?secret={}",
+            kind,
+            percent_encode(&self.label),
+            base32_encode(hotp.secret())
+        );
+        if let Some(ref issuer) = self.issuer {
+            uri.push_str("&issuer=");
+            uri.push_str(&percent_encode(issuer));
+        }
+        uri.push_str("&algorithm=");
+        uri.push_str(algorithm_name(hotp.algorithm()));
+        uri.push_str(&format!("&digits={}", hotp.digits()));
+        match self.otp {
+            Otp::Hotp(_, counter) => uri.push_str(&format!("&counter={}", counter)),
+            Otp::Totp(ref totp) => uri.push_str(&format!("&period={}", totp.step().as_secs())),
+        }
+        uri
+    }
+}
+
+/// The name of an HMAC algorithm in an `otpauth://` URI.
+fn algorithm_name(algorithm: CCHmacAlgorithm) -> &'static str {
+    match algorithm {
+        CCHmacAlgorithm::kCCHmacAlgSHA1 => "SHA1",
+        CCHmacAlgorithm::kCCHmacAlgMD5 => "MD5",
+        CCHmacAlgorithm::kCCHmacAlgSHA256 => "SHA256",
+        CCHmacAlgorithm::kCCHmacAlgSHA384 => "SHA384",
+        CCHmacAlgorithm::kCCHmacAlgSHA512 => "SHA512",
+        CCHmacAlgorithm::kCCHmacAlgSHA224 => "SHA224",
+    }
+}
+
+/// Decodes a Base32 secret, ignoring case, padding and spaces, as authenticator apps do.
+fn decode_secret(encoded: &str) -> Result<SecretBytes> {
+    let normalized: String = encoded
+        .chars()
+        .filter(|c| *c != '=' && *c != ' ')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    base32_decode(&normalized).map(SecretBytes::from)
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Decodes percent-encoded UTF-8. In query values, `+` is also decoded as a space.
+fn percent_decode(encoded: &str, plus_as_space: bool) -> Result<String> {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = encoded
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.bytes().all(|c| c.is_ascii_hexdigit()))
+                    .ok_or(Error::DecodeError)?;
+                decoded.push(u8::from_str_radix(hex, 16).map_err(|_| Error::DecodeError)?);
+                i += 3;
+            }
+            b'+' if plus_as_space => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| Error::DecodeError)
+}
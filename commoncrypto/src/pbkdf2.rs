@@ -20,37 +20,60 @@
 
 //! Idiomatic Rust wrapper for `CommonCrypto`'s `CCKeyDerivationPBKDF` function.
 
-use commoncrypto_sys::{CCKeyDerivationPBKDF, CCPBKDFAlgorithm};
-
-use std::io;
+use commoncrypto_sys::{CCCalibratePBKDF, CCKeyDerivationPBKDF, CCPBKDFAlgorithm};
+use error::check_status;
+use secret::SecretBytes;
+use std::time::Duration;
+use {Error, Result};
 
 pub use commoncrypto_sys::CCPseudoRandomAlgorithm;
 
-macro_rules! err_from_cckeyderivationpbkdf_retval {
-    ($func_name: expr, $val: expr) => {{
-        let kind = match $val {
-            // kCCParamError is the only one that's specifically noted
-            -43000 => io::ErrorKind::InvalidInput,
-            _ => io::ErrorKind::Other,
-        };
+#[cfg(feature = "password-hash")]
+mod password_hash;
+mod phc;
 
-        Err(io::Error::new(
-            kind,
-            format!("{} returned nonzero: {}", $func_name, $val),
-        ))
-    }};
-}
+#[cfg(feature = "password-hash")]
+pub use self::password_hash::Pbkdf2;
+pub use self::phc::{hash_password, needs_rehash, verify_password, SALT_LEN};
 
-/// Derive a key from a password or passphrase and a salt
+/// Derive a key from a password or passphrase and a salt. The key is wiped when it is dropped.
 pub fn pbkdf2(
     password: &[u8],
     salt: &[u8],
     prf: CCPseudoRandomAlgorithm,
     rounds: u32,
     key_len: usize,
-) -> io::Result<Vec<u8>> {
-    let mut pw_derived = vec![0u8; key_len];
-    let result = unsafe {
+) -> Result<SecretBytes> {
+    let mut pw_derived = SecretBytes::new(key_len);
+    derive_into(password, salt, prf, rounds, &mut pw_derived)?;
+    Ok(pw_derived)
+}
+
+/// Derive a key from a password or passphrase and a salt into `out`, without allocating. The
+/// length of the key is the length of `out`.
+///
+/// If `out` is empty, `Error::ParamError` is returned, as `pbkdf2` does for a zero `key_len`.
+pub fn pbkdf2_into(
+    password: &[u8],
+    salt: &[u8],
+    prf: CCPseudoRandomAlgorithm,
+    rounds: u32,
+    out: &mut [u8],
+) -> Result<()> {
+    derive_into(password, salt, prf, rounds, out)
+}
+
+fn derive_into(
+    password: &[u8],
+    salt: &[u8],
+    prf: CCPseudoRandomAlgorithm,
+    rounds: u32,
+    out: &mut [u8],
+) -> Result<()> {
+    if out.is_empty() {
+        return Err(Error::ParamError);
+    }
+    check_status(unsafe {
         CCKeyDerivationPBKDF(
             CCPBKDFAlgorithm::kCCPBKDF2,
             password.as_ptr(),
@@ -59,14 +82,84 @@ pub fn pbkdf2(
             salt.len(),
             prf,
             rounds,
-            pw_derived.as_mut_ptr(),
-            pw_derived.len(),
+            out.as_mut_ptr(),
+            out.len(),
         )
-    };
+    })
+}
 
-    if result == 0 {
-        Ok(pw_derived)
+/// Determines the number of rounds for which `pbkdf2` takes about `target` on the current host,
+/// for passwords, salts and keys of the given lengths.
+pub fn calibrate(
+    prf: CCPseudoRandomAlgorithm,
+    password_len: usize,
+    salt_len: usize,
+    target: Duration,
+    key_len: usize,
+) -> Result<u32> {
+    let msec = target.as_millis().min(u128::from(u32::MAX)) as u32;
+    let rounds = unsafe {
+        CCCalibratePBKDF(
+            CCPBKDFAlgorithm::kCCPBKDF2,
+            password_len,
+            salt_len,
+            prf,
+            key_len,
+            msec,
+        )
+    };
+    // CCCalibratePBKDF returns -1 if the parameters are invalid
+    if rounds == u32::MAX {
+        Err(Error::ParamError)
     } else {
-        err_from_cckeyderivationpbkdf_retval!("CCKeyDerivationPBKDF", result)
+        Ok(rounds)
+    }
+}
+
+/// Parameters for deriving keys with `pbkdf2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pbkdf2Params {
+    /// Pseudo-random function
+    pub prf: CCPseudoRandomAlgorithm,
+    /// Number of rounds
+    pub rounds: u32,
+    /// Number of bytes in the derived key
+    pub key_len: usize,
+}
+
+impl Pbkdf2Params {
+    /// Number of HMAC-SHA256 rounds recommended by OWASP as of 2023.
+    pub const RECOMMENDED_ROUNDS: u32 = 600_000;
+
+    /// Creates parameters whose number of rounds takes about `target` on the current host, for
+    /// passwords and salts of the given lengths. See `calibrate`.
+    pub fn calibrated(
+        prf: CCPseudoRandomAlgorithm,
+        password_len: usize,
+        salt_len: usize,
+        target: Duration,
+        key_len: usize,
+    ) -> Result<Pbkdf2Params> {
+        Ok(Pbkdf2Params {
+            prf,
+            rounds: calibrate(prf, password_len, salt_len, target, key_len)?,
+            key_len,
+        })
+    }
+
+    /// Derives a key from `password` and `salt` with these parameters.
+    pub fn derive(&self, password: &[u8], salt: &[u8]) -> Result<SecretBytes> {
+        pbkdf2(password, salt, self.prf, self.rounds, self.key_len)
+    }
+}
+
+impl Default for Pbkdf2Params {
+    /// HMAC-SHA256 with the recommended number of rounds, deriving 32-byte keys.
+    fn default() -> Pbkdf2Params {
+        Pbkdf2Params {
+            prf: CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256,
+            rounds: Pbkdf2Params::RECOMMENDED_ROUNDS,
+            key_len: 32,
+        }
     }
 }
@@ -0,0 +1,114 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Implementation of the `RustCrypto` `password-hash` traits, backed by `CCKeyDerivationPBKDF`.
+
+use super::phc::{algorithm_id, prf_from_id};
+use super::{pbkdf2, Pbkdf2Params};
+use password_hash::errors::InvalidValue;
+use password_hash::{
+    Decimal, Error, Ident, Output, ParamsString, PasswordHash, PasswordHasher, Result, Salt,
+};
+use std::convert::TryFrom;
+
+/// PBKDF2 as a `password-hash` `PasswordHasher`, and so also a `PasswordVerifier`.
+///
+/// The hashes are compatible with `hash_password` and `verify_password`. Requires the
+/// `password-hash` feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pbkdf2;
+
+impl PasswordHasher for Pbkdf2 {
+    type Params = Pbkdf2Params;
+
+    /// Hashes `password`. The pseudo-random function is taken from `algorithm` if it is given,
+    /// and from `params` otherwise.
+    fn hash_password_customized<'a>(
+        &self,
+        password: &[u8],
+        algorithm: Option<Ident<'a>>,
+        version: Option<Decimal>,
+        params: Pbkdf2Params,
+        salt: impl Into<Salt<'a>>,
+    ) -> Result<PasswordHash<'a>> {
+        if version.is_some() {
+            return Err(Error::Version);
+        }
+        let prf = match algorithm {
+            Some(algorithm) => prf_from_id(algorithm.as_str()).ok_or(Error::Algorithm)?,
+            None => params.prf,
+        };
+        let params = Pbkdf2Params { prf, ..params };
+        let salt = salt.into();
+        let mut salt_buf = [0u8; Salt::MAX_LENGTH];
+        let salt_bytes = salt.decode_b64(&mut salt_buf)?;
+        let hash = pbkdf2(password, salt_bytes, prf, params.rounds, params.key_len)
+            .map_err(|_| Error::Crypto)?;
+        Ok(PasswordHash {
+            algorithm: Ident::new(algorithm_id(prf))?,
+            version: None,
+            params: ParamsString::try_from(params)?,
+            salt: Some(salt),
+            hash: Some(Output::new(&hash)?),
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a PasswordHash<'a>> for Pbkdf2Params {
+    type Error = Error;
+
+    fn try_from(hash: &'a PasswordHash<'a>) -> Result<Pbkdf2Params> {
+        if hash.version.is_some() {
+            return Err(Error::Version);
+        }
+        let mut params = Pbkdf2Params {
+            prf: prf_from_id(hash.algorithm.as_str()).ok_or(Error::Algorithm)?,
+            ..Pbkdf2Params::default()
+        };
+        if let Some(ref output) = hash.hash {
+            params.key_len = output.len();
+        }
+        for (name, value) in hash.params.iter() {
+            match name.as_str() {
+                "i" => params.rounds = value.decimal()?,
+                "l" => {
+                    let key_len = value.decimal()? as usize;
+                    if params.key_len != key_len && hash.hash.is_some() {
+                        return Err(InvalidValue::Malformed.param_error());
+                    }
+                    params.key_len = key_len;
+                }
+                _ => return Err(Error::ParamNameInvalid),
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl TryFrom<Pbkdf2Params> for ParamsString {
+    type Error = Error;
+
+    fn try_from(params: Pbkdf2Params) -> Result<ParamsString> {
+        let mut string = ParamsString::new();
+        string.add_decimal("i", params.rounds)?;
+        string.add_decimal("l", params.key_len as Decimal)?;
+        Ok(string)
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Password hashes in the PHC string format, such as `$pbkdf2-sha256$i=600000,l=32$salt$hash`.
+//!
+//! The salt and hash are encoded in unpadded standard Base64, as in the `pbkdf2` crate, so hashes
+//! can be exchanged with it.
+
+use super::{pbkdf2, CCPseudoRandomAlgorithm, Pbkdf2Params};
+use constant_time;
+use encoding::{base64_decode, base64_encode};
+use random;
+use {Error, Result};
+
+/// Number of bytes in the random salt of a new password hash.
+pub const SALT_LEN: usize = 16;
+
+/// The PHC algorithm identifier for PBKDF2 with `prf`.
+pub fn algorithm_id(prf: CCPseudoRandomAlgorithm) -> &'static str {
+    match prf {
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1 => "pbkdf2",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA224 => "pbkdf2-sha224",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256 => "pbkdf2-sha256",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA384 => "pbkdf2-sha384",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA512 => "pbkdf2-sha512",
+    }
+}
+
+/// The pseudo-random function of a PHC algorithm identifier, if it is a PBKDF2 one.
+pub fn prf_from_id(id: &str) -> Option<CCPseudoRandomAlgorithm> {
+    match id {
+        "pbkdf2" => Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1),
+        "pbkdf2-sha224" => Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA224),
+        "pbkdf2-sha256" => Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256),
+        "pbkdf2-sha384" => Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA384),
+        "pbkdf2-sha512" => Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA512),
+        _ => None,
+    }
+}
+
+/// A parsed PBKDF2 password hash.
+struct PasswordHash {
+    params: Pbkdf2Params,
+    salt: Vec<u8>,
+    hash: Vec<u8>,
+}
+
+impl PasswordHash {
+    fn parse(phc: &str) -> Result<PasswordHash> {
+        let fields: Vec<&str> = phc.split('$').collect();
+        let (id, params, salt, hash) = match fields[..] {
+            ["", id, params, salt, hash] => (id, params, salt, hash),
+            _ => return Err(Error::DecodeError),
+        };
+        let prf = prf_from_id(id).ok_or(Error::DecodeError)?;
+        let salt = base64_decode(salt, false)?;
+        let hash = base64_decode(hash, false)?;
+        let mut rounds = None;
+        let mut key_len = None;
+        for param in params.split(',') {
+            let (name, value) = match param.find('=') {
+                Some(i) => (&param[..i], &param[i + 1..]),
+                None => return Err(Error::DecodeError),
+            };
+            let value: u32 = value.parse().map_err(|_| Error::DecodeError)?;
+            match name {
+                "i" if rounds.is_none() => rounds = Some(value),
+                "l" if key_len.is_none() => key_len = Some(value as usize),
+                _ => return Err(Error::DecodeError),
+            }
+        }
+        let rounds = rounds.ok_or(Error::DecodeError)?;
+        let key_len = key_len.unwrap_or(hash.len());
+        if rounds == 0 || hash.is_empty() || key_len != hash.len() {
+            return Err(Error::DecodeError);
+        }
+        Ok(PasswordHash {
+            params: Pbkdf2Params {
+                prf,
+                rounds,
+                key_len,
+            },
+            salt,
+            hash,
+        })
+    }
+}
+
+/// Formats a PBKDF2 password hash as a PHC string.
+pub fn format(params: &Pbkdf2Params, salt: &[u8], hash: &[u8]) -> String {
+    format!(
+        "${}$i={},l={}${}${}",
+        algorithm_id(params.prf),
+        params.rounds,
+        params.key_len,
+        base64_encode(salt, false),
+        base64_encode(hash, false)
+    )
+}
+
+/// Hashes `password` with `params` and a random salt, as a PHC string such as
+/// `$pbkdf2-sha256$i=600000,l=32$salt$hash`.
+pub fn hash_password(password: &[u8], params: &Pbkdf2Params) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    random::fill(&mut salt)?;
+    let hash = pbkdf2(password, &salt, params.prf, params.rounds, params.key_len)?;
+    Ok(format(params, &salt, &hash))
+}
+
+/// Checks whether `password` matches the PHC string `phc`, comparing the hashes in constant time.
+///
+/// Returns `Error::DecodeError` if `phc` is not a PBKDF2 password hash.
+pub fn verify_password(password: &[u8], phc: &str) -> Result<bool> {
+    let parsed = PasswordHash::parse(phc)?;
+    let hash = parsed.params.derive(password, &parsed.salt)?;
+    Ok(constant_time::eq(&hash, &parsed.hash))
+}
+
+/// Checks whether the PHC string `phc` was hashed with weaker or different parameters than
+/// `params`, i.e. with a different pseudo-random function or key length, or with fewer rounds.
+///
+/// Returns `Error::DecodeError` if `phc` is not a PBKDF2 password hash.
+pub fn needs_rehash(phc: &str, params: &Pbkdf2Params) -> Result<bool> {
+    let parsed = PasswordHash::parse(phc)?.params;
+    Ok(parsed.prf != params.prf
+        || parsed.key_len != params.key_len
+        || parsed.rounds < params.rounds)
+}
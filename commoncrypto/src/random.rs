@@ -0,0 +1,73 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Idiomatic Rust wrapper for `CommonCrypto`'s `CCRandomGenerateBytes` function.
+
+use commoncrypto_sys::CCRandomGenerateBytes;
+use error::check_status;
+#[cfg(feature = "rand_core")]
+use std::num::NonZeroU32;
+use Result;
+
+/// Fills `buf` with cryptographically strong random bytes.
+pub fn fill(buf: &mut [u8]) -> Result<()> {
+    check_status(unsafe { CCRandomGenerateBytes(buf.as_mut_ptr(), buf.len()) })
+}
+
+/// Generates `len` cryptographically strong random bytes.
+pub fn random_bytes(len: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    fill(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Cryptographically secure random number generator backed by `CCRandomGenerateBytes`.
+///
+/// Requires the `rand_core` feature.
+#[cfg(feature = "rand_core")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CommonCryptoRng;
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for CommonCryptoRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(err) = self.try_fill_bytes(dest) {
+            panic!("CCRandomGenerateBytes failed: {}", err);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> ::std::result::Result<(), rand_core::Error> {
+        fill(dest).map_err(|_| {
+            let code = NonZeroU32::new(rand_core::Error::CUSTOM_START).expect("nonzero");
+            rand_core::Error::from(code)
+        })
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::CryptoRng for CommonCryptoRng {}
@@ -0,0 +1,187 @@
+// Copyright (c) 2026 Mark Lee
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.  IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN
+// THE SOFTWARE.
+
+//! Buffers for secrets, such as derived keys, which are wiped when they are dropped.
+
+use constant_time;
+use std::fmt;
+#[cfg(unix)]
+use std::io;
+use std::ops::{Deref, DerefMut};
+#[cfg(unix)]
+use std::os::raw::{c_int, c_void};
+use std::ptr;
+use std::sync::atomic::{self, Ordering};
+
+#[cfg(unix)]
+extern "C" {
+    fn mlock(addr: *const c_void, len: usize) -> c_int;
+    fn munlock(addr: *const c_void, len: usize) -> c_int;
+}
+
+/// Overwrites `bytes` with zeros, in a way which the compiler does not optimize away.
+pub(crate) fn wipe(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        unsafe { ptr::write_volatile(byte, 0) };
+    }
+    atomic::compiler_fence(Ordering::SeqCst);
+}
+
+/// A fixed-size byte buffer for secrets, which is zeroed when it is dropped.
+///
+/// Its contents are not shown by `Debug`, and it is compared in constant time. It dereferences to
+/// `[u8]`, so it can be passed to functions which take key material as a slice.
+pub struct SecretBytes {
+    bytes: Box<[u8]>,
+    locked: bool,
+}
+
+impl SecretBytes {
+    /// Creates a buffer of `len` zeros.
+    pub fn new(len: usize) -> SecretBytes {
+        SecretBytes {
+            bytes: vec![0; len].into_boxed_slice(),
+            locked: false,
+        }
+    }
+
+    /// Copies `bytes` into a new buffer. The original is left as-is.
+    pub fn from_slice(bytes: &[u8]) -> SecretBytes {
+        SecretBytes {
+            bytes: bytes.to_vec().into_boxed_slice(),
+            locked: false,
+        }
+    }
+
+    /// The secret bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The secret bytes, mutably.
+    pub fn as_mut_bytes(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    /// Locks the buffer into memory with `mlock`, so that it is not written to swap. It is
+    /// unlocked when the buffer is dropped.
+    ///
+    /// The number of bytes a process can lock is usually limited (see `ulimit -l`).
+    #[cfg(unix)]
+    pub fn lock(&mut self) -> io::Result<()> {
+        if self.locked || self.bytes.is_empty() {
+            return Ok(());
+        }
+        if unsafe { mlock(self.bytes.as_ptr() as *const c_void, self.bytes.len()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        self.locked = true;
+        Ok(())
+    }
+
+    /// Whether the buffer has been locked into memory with `lock`.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    /// Takes ownership of `bytes`. Spare capacity is wiped rather than released with the secret
+    /// still in it.
+    fn from(mut bytes: Vec<u8>) -> SecretBytes {
+        if bytes.len() == bytes.capacity() {
+            return SecretBytes {
+                bytes: bytes.into_boxed_slice(),
+                locked: false,
+            };
+        }
+        let secret = SecretBytes::from_slice(&bytes);
+        wipe(&mut bytes);
+        secret
+    }
+}
+
+impl Clone for SecretBytes {
+    /// Copies the secret into a new buffer, which is not locked into memory.
+    fn clone(&self) -> SecretBytes {
+        SecretBytes::from_slice(&self.bytes)
+    }
+}
+
+impl Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl DerefMut for SecretBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl PartialEq for SecretBytes {
+    /// Compares the secrets in constant time. Only their lengths are compared in variable time.
+    fn eq(&self, other: &SecretBytes) -> bool {
+        constant_time::eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SecretBytes")
+            .field("len", &self.bytes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        wipe(&mut self.bytes);
+        #[cfg(unix)]
+        {
+            if self.locked {
+                unsafe { munlock(self.bytes.as_ptr() as *const c_void, self.bytes.len()) };
+            }
+        }
+    }
+}
+
+/// Requires the `zeroize` feature.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        wipe(&mut self.bytes);
+    }
+}
+
+/// Requires the `zeroize` feature.
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretBytes {}
@@ -0,0 +1,172 @@
+extern crate commoncrypto;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_sys;
+extern crate hex;
+
+use commoncrypto::aead::{AesGcm, TAG_LEN};
+use commoncrypto::Error;
+
+// These keys, nonces, plaintexts, ciphertexts and tags come from test cases 1-4 in "The
+// Galois/Counter Mode of Operation (GCM)", as used in NIST's GCM validation
+const KEY: &str = "feffe9928665731c6d6a8f9467308308";
+const NONCE: &str = "cafebabefacedbaddecaf888";
+const AAD: &str = "feedfacedeadbeeffeedfacedeadbeefabaddad2";
+const PLAINTEXT: &str = concat!(
+    "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72",
+    "1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255"
+);
+const CIPHERTEXT: &str = concat!(
+    "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e",
+    "21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985"
+);
+const TAG: &str = "4d5c2af327cd64a62cf35abd2ba6fab4";
+const TAG_WITH_AAD: &str = "5bc94fbc3221a5db94fae95ae7121a47";
+
+macro_rules! test_aes_gcm {
+    (
+        $test_name: ident,
+        $key: expr,
+        $nonce: expr,
+        $aad: expr,
+        $plaintext: expr,
+        $ciphertext: expr,
+        $tag: expr
+    ) => {
+        #[test]
+        fn $test_name() {
+            let gcm = AesGcm::new(&hex::decode($key).unwrap()).expect("valid key");
+            let nonce = hex::decode($nonce).unwrap();
+            let aad = hex::decode($aad).unwrap();
+            let plaintext = hex::decode($plaintext).unwrap();
+            let expected = format!("{}{}", $ciphertext, $tag);
+
+            let sealed = gcm.encrypt(&nonce, &aad, &plaintext).expect("encrypt");
+            assert_eq!(hex::encode(&sealed), expected);
+            let opened = gcm.decrypt(&nonce, &aad, &sealed).expect("decrypt");
+            assert_eq!(opened, plaintext);
+        }
+    };
+}
+
+test_aes_gcm!(
+    aes_gcm_empty,
+    "00000000000000000000000000000000",
+    "000000000000000000000000",
+    "",
+    "",
+    "",
+    "58e2fccefa7e3061367f1d57a4e7455a"
+);
+test_aes_gcm!(
+    aes_gcm_zero_block,
+    "00000000000000000000000000000000",
+    "000000000000000000000000",
+    "",
+    "00000000000000000000000000000000",
+    "0388dace60b6a392f328c2b971b2fe78",
+    "ab6e47d42cec13bdf53a67b21257bddf"
+);
+test_aes_gcm!(aes_gcm_blocks, KEY, NONCE, "", PLAINTEXT, CIPHERTEXT, TAG);
+test_aes_gcm!(
+    aes_gcm_aad,
+    KEY,
+    NONCE,
+    AAD,
+    &PLAINTEXT[..120],
+    &CIPHERTEXT[..120],
+    TAG_WITH_AAD
+);
+
+#[test]
+fn aes_gcm_in_place_detached() {
+    let gcm = AesGcm::new(&hex::decode(KEY).unwrap()).expect("valid key");
+    let nonce = hex::decode(NONCE).unwrap();
+    let mut buffer = hex::decode(PLAINTEXT).unwrap();
+    let tag = gcm
+        .encrypt_in_place_detached(&nonce, b"", &mut buffer)
+        .expect("encrypt");
+    assert_eq!(hex::encode(&buffer), CIPHERTEXT);
+    assert_eq!(hex::encode(tag), TAG);
+    gcm.decrypt_in_place_detached(&nonce, b"", &mut buffer, &tag)
+        .expect("decrypt");
+    assert_eq!(hex::encode(&buffer), PLAINTEXT);
+}
+
+#[test]
+fn aes_gcm_tampered_tag() {
+    let gcm = AesGcm::new(&hex::decode(KEY).unwrap()).expect("valid key");
+    let nonce = hex::decode(NONCE).unwrap();
+    let mut buffer = hex::decode(CIPHERTEXT).unwrap();
+    let mut tag = hex::decode(TAG).unwrap();
+    tag[TAG_LEN - 1] ^= 1;
+    assert_eq!(
+        gcm.decrypt_in_place_detached(&nonce, b"", &mut buffer, &tag),
+        Err(Error::DecodeError)
+    );
+    assert!(buffer.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn aes_gcm_tampered_aad() {
+    let gcm = AesGcm::new(&hex::decode(KEY).unwrap()).expect("valid key");
+    let nonce = hex::decode(NONCE).unwrap();
+    let sealed = gcm.encrypt(&nonce, b"header", b"message").expect("encrypt");
+    assert_eq!(
+        gcm.decrypt(&nonce, b"headex", &sealed),
+        Err(Error::DecodeError)
+    );
+    assert_eq!(
+        gcm.decrypt(&nonce, b"header", &sealed[..TAG_LEN - 1]),
+        Err(Error::DecodeError)
+    );
+}
+
+#[test]
+fn aes_gcm_invalid_key_size() {
+    assert_eq!(AesGcm::new(&[0u8; 15]).unwrap_err(), Error::KeySizeError);
+}
+
+#[cfg(not(target_vendor = "apple"))]
+mod injected_failures {
+    use super::{CIPHERTEXT, KEY, NONCE, PLAINTEXT, TAG};
+    use commoncrypto::aead::AesGcm;
+    use commoncrypto::Error;
+    use commoncrypto_shim::{clear_failures, inject_failure};
+    use commoncrypto_sys::CCCryptorStatus;
+    use std::os::raw::c_int;
+
+    #[test]
+    fn encrypt_failure() {
+        let gcm = AesGcm::new(&hex::decode(KEY).unwrap()).expect("valid key");
+        let nonce = hex::decode(NONCE).unwrap();
+        inject_failure(
+            "CCCryptorGCMOneshotEncrypt",
+            CCCryptorStatus::kCCUnspecifiedError as c_int,
+        );
+        assert_eq!(
+            gcm.encrypt(&nonce, b"", &hex::decode(PLAINTEXT).unwrap()),
+            Err(Error::UnspecifiedError)
+        );
+        clear_failures();
+    }
+
+    #[test]
+    fn decrypt_failure_wipes_buffer() {
+        let gcm = AesGcm::new(&hex::decode(KEY).unwrap()).expect("valid key");
+        let nonce = hex::decode(NONCE).unwrap();
+        let tag = hex::decode(TAG).unwrap();
+        let mut buffer = hex::decode(CIPHERTEXT).unwrap();
+        inject_failure(
+            "CCCryptorGCMOneshotDecrypt",
+            CCCryptorStatus::kCCUnspecifiedError as c_int,
+        );
+        assert_eq!(
+            gcm.decrypt_in_place_detached(&nonce, b"", &mut buffer, &tag),
+            Err(Error::UnspecifiedError)
+        );
+        assert!(buffer.iter().all(|&b| b == 0));
+        clear_failures();
+    }
+}
@@ -0,0 +1,270 @@
+extern crate commoncrypto;
+extern crate hex;
+
+use commoncrypto::cryptor::{
+    decrypt, decrypt_into, encrypt, encrypt_into, kCCModeOptionCTR_BE, kCCOptionECBMode,
+    kCCOptionPKCS7Padding, CCAlgorithm, CCMode, CCOperation, CCPadding, Cryptor,
+};
+use commoncrypto::Error;
+
+// These keys, IVs, plaintext and ciphertexts come from the AES-128 test vectors in
+// NIST SP 800-38A, Appendix F
+const KEY: &str = "2b7e151628aed2a6abf7158809cf4f3c";
+const IV: &str = "000102030405060708090a0b0c0d0e0f";
+const COUNTER: &str = "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
+const PLAINTEXT: &str = concat!(
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710"
+);
+const ECB_CIPHERTEXT: &str = concat!(
+    "3ad77bb40d7a3660a89ecaf32466ef97f5d3d58503b9699de785895a96fdbaaf",
+    "43b1cd7f598ece23881b00e3ed0306887b0c785e27e8ad3f8223207104725dd4"
+);
+const CBC_CIPHERTEXT: &str = concat!(
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2",
+    "73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7"
+);
+const CTR_CIPHERTEXT: &str = concat!(
+    "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff",
+    "5ae4df3edbd5d35e5b4f09020db03eab1e031dda2fbe03d1792170a0f3009cee"
+);
+
+fn crypt_in_chunks(mut cryptor: Cryptor, data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    for chunk in data.chunks(7) {
+        output.extend(cryptor.update(chunk).expect("update failed"));
+    }
+    output.extend(cryptor.finish().expect("finish failed"));
+    output
+}
+
+fn ecb_cryptor(op: CCOperation) -> Cryptor {
+    let key = hex::decode(KEY).unwrap();
+    Cryptor::new(
+        op,
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionECBMode,
+        &key,
+        None,
+    )
+    .expect("CCCryptorCreate failed")
+}
+
+fn cbc_cryptor(op: CCOperation) -> Cryptor {
+    let key = hex::decode(KEY).unwrap();
+    let iv = hex::decode(IV).unwrap();
+    Cryptor::new(op, CCAlgorithm::kCCAlgorithmAES, 0, &key, Some(&iv))
+        .expect("CCCryptorCreate failed")
+}
+
+fn ctr_cryptor(op: CCOperation) -> Cryptor {
+    let key = hex::decode(KEY).unwrap();
+    let counter = hex::decode(COUNTER).unwrap();
+    Cryptor::with_mode(
+        op,
+        CCMode::kCCModeCTR,
+        CCAlgorithm::kCCAlgorithmAES,
+        CCPadding::ccNoPadding,
+        &key,
+        Some(&counter),
+        kCCModeOptionCTR_BE,
+    )
+    .expect("CCCryptorCreateWithMode failed")
+}
+
+#[test]
+fn aes_ecb_encrypt() {
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let ciphertext = crypt_in_chunks(ecb_cryptor(CCOperation::kCCEncrypt), &plaintext);
+    assert_eq!(hex::encode(ciphertext), ECB_CIPHERTEXT);
+}
+
+#[test]
+fn aes_ecb_decrypt() {
+    let ciphertext = hex::decode(ECB_CIPHERTEXT).unwrap();
+    let plaintext = crypt_in_chunks(ecb_cryptor(CCOperation::kCCDecrypt), &ciphertext);
+    assert_eq!(hex::encode(plaintext), PLAINTEXT);
+}
+
+#[test]
+fn aes_cbc_encrypt() {
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let ciphertext = crypt_in_chunks(cbc_cryptor(CCOperation::kCCEncrypt), &plaintext);
+    assert_eq!(hex::encode(ciphertext), CBC_CIPHERTEXT);
+}
+
+#[test]
+fn aes_cbc_decrypt() {
+    let ciphertext = hex::decode(CBC_CIPHERTEXT).unwrap();
+    let plaintext = crypt_in_chunks(cbc_cryptor(CCOperation::kCCDecrypt), &ciphertext);
+    assert_eq!(hex::encode(plaintext), PLAINTEXT);
+}
+
+#[test]
+fn aes_ctr_encrypt() {
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let ciphertext = crypt_in_chunks(ctr_cryptor(CCOperation::kCCEncrypt), &plaintext);
+    assert_eq!(hex::encode(ciphertext), CTR_CIPHERTEXT);
+}
+
+#[test]
+fn aes_ctr_decrypt() {
+    let ciphertext = hex::decode(CTR_CIPHERTEXT).unwrap();
+    let plaintext = crypt_in_chunks(ctr_cryptor(CCOperation::kCCDecrypt), &ciphertext);
+    assert_eq!(hex::encode(plaintext), PLAINTEXT);
+}
+
+#[test]
+fn aes_cbc_pkcs7_roundtrip() {
+    let key = hex::decode(KEY).unwrap();
+    let iv = hex::decode(IV).unwrap();
+    let message = b"The quick brown fox jumps over the lazy dog";
+    let encryptor = Cryptor::new(
+        CCOperation::kCCEncrypt,
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionPKCS7Padding,
+        &key,
+        Some(&iv),
+    )
+    .expect("CCCryptorCreate failed");
+    let ciphertext = crypt_in_chunks(encryptor, message);
+    assert_eq!(ciphertext.len(), 48);
+    let decryptor = Cryptor::new(
+        CCOperation::kCCDecrypt,
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionPKCS7Padding,
+        &key,
+        Some(&iv),
+    )
+    .expect("CCCryptorCreate failed");
+    assert_eq!(crypt_in_chunks(decryptor, &ciphertext), message.to_vec());
+}
+
+#[test]
+fn cryptor_reuse_after_finish() {
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let iv = hex::decode(IV).unwrap();
+    let mut cryptor = cbc_cryptor(CCOperation::kCCEncrypt);
+    let mut ciphertext = cryptor.update(&plaintext).expect("update failed");
+    ciphertext.extend(cryptor.finish().expect("finish failed"));
+    assert_eq!(hex::encode(ciphertext), CBC_CIPHERTEXT);
+
+    // Finishing does not restart with the same IV
+    assert_eq!(cryptor.update(&plaintext), Err(Error::CallSequenceError));
+    assert_eq!(cryptor.finish(), Err(Error::CallSequenceError));
+    assert_eq!(cryptor.reset(Some(b"short")), Err(Error::ParamError));
+
+    cryptor.reset(Some(&iv)).expect("reset failed");
+    let mut ciphertext = cryptor.update(&plaintext).expect("update failed");
+    ciphertext.extend(cryptor.finish().expect("finish failed"));
+    assert_eq!(hex::encode(ciphertext), CBC_CIPHERTEXT);
+}
+
+#[test]
+fn invalid_iv_length() {
+    let key = hex::decode(KEY).unwrap();
+    let result = Cryptor::new(
+        CCOperation::kCCEncrypt,
+        CCAlgorithm::kCCAlgorithmAES,
+        0,
+        &key,
+        Some(b"short"),
+    );
+    assert_eq!(result.unwrap_err(), Error::ParamError);
+}
+
+#[test]
+fn aes_ecb_oneshot() {
+    let key = hex::decode(KEY).unwrap();
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let ciphertext = encrypt(
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionECBMode,
+        &key,
+        None,
+        &plaintext,
+    )
+    .expect("CCCrypt failed");
+    assert_eq!(hex::encode(&ciphertext), ECB_CIPHERTEXT);
+    let decrypted = decrypt(
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionECBMode,
+        &key,
+        None,
+        &ciphertext,
+    )
+    .expect("CCCrypt failed");
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn aes_cbc_oneshot_into() {
+    let key = hex::decode(KEY).unwrap();
+    let iv = hex::decode(IV).unwrap();
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let mut ciphertext = [0u8; 64];
+    let written = encrypt_into(
+        CCAlgorithm::kCCAlgorithmAES,
+        0,
+        &key,
+        Some(&iv),
+        &plaintext,
+        &mut ciphertext,
+    )
+    .expect("CCCrypt failed");
+    assert_eq!(written, 64);
+    assert_eq!(hex::encode(&ciphertext[..]), CBC_CIPHERTEXT);
+    let mut decrypted = [0u8; 64];
+    let written = decrypt_into(
+        CCAlgorithm::kCCAlgorithmAES,
+        0,
+        &key,
+        Some(&iv),
+        &ciphertext,
+        &mut decrypted,
+    )
+    .expect("CCCrypt failed");
+    assert_eq!(written, 64);
+    assert_eq!(hex::encode(&decrypted[..]), PLAINTEXT);
+}
+
+#[test]
+fn oneshot_into_buffer_too_small() {
+    let key = hex::decode(KEY).unwrap();
+    let plaintext = hex::decode(PLAINTEXT).unwrap();
+    let mut ciphertext = [0u8; 16];
+    assert!(encrypt_into(
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionECBMode,
+        &key,
+        None,
+        &plaintext,
+        &mut ciphertext,
+    )
+    .is_err());
+}
+
+#[test]
+fn aes_cbc_pkcs7_oneshot_roundtrip() {
+    let key = hex::decode(KEY).unwrap();
+    let iv = hex::decode(IV).unwrap();
+    let message = b"The quick brown fox jumps over the lazy dog";
+    let ciphertext = encrypt(
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionPKCS7Padding,
+        &key,
+        Some(&iv),
+        message,
+    )
+    .expect("CCCrypt failed");
+    assert_eq!(ciphertext.len(), 48);
+    let decrypted = decrypt(
+        CCAlgorithm::kCCAlgorithmAES,
+        kCCOptionPKCS7Padding,
+        &key,
+        Some(&iv),
+        &ciphertext,
+    )
+    .expect("CCCrypt failed");
+    assert_eq!(decrypted, message.to_vec());
+}
@@ -0,0 +1,24 @@
+extern crate commoncrypto;
+
+use commoncrypto::Error;
+use std::io;
+
+#[test]
+fn from_status() {
+    assert_eq!(Error::from_status(-4300), Error::ParamError);
+    assert_eq!(Error::from_status(-4301), Error::BufferTooSmall);
+    assert_eq!(Error::from_status(-4304), Error::DecodeError);
+    assert_eq!(Error::from_status(-4311), Error::InvalidKey);
+    assert_eq!(Error::from_status(-43000), Error::Unknown(-43000));
+}
+
+#[test]
+fn into_io_error() {
+    let err: io::Error = Error::ParamError.into();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<Error>());
+    assert_eq!(inner, Some(&Error::ParamError));
+
+    let err: io::Error = Error::DecodeError.into();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
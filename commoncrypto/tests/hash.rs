@@ -1,11 +1,39 @@
+#![allow(clippy::redundant_static_lifetimes)]
+
 extern crate commoncrypto;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_sys;
+#[cfg(feature = "digest")]
+extern crate digest;
+#[cfg(feature = "futures-io")]
+extern crate futures_io;
 extern crate hex;
+#[cfg(feature = "digest")]
+extern crate hmac;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "tokio")]
+extern crate tokio;
 
-use commoncrypto::hash::{CCDigestAlgorithm, Hasher};
+use commoncrypto::hash::{
+    algorithms, digest_info, is_supported, Algorithm, CCDigestAlgorithm, Digest, HashContext,
+    HashState, Hasher, HasherPool, Md5Digest, PooledHasher, ResumableMd5, ResumableSha1,
+    ResumableSha224, ResumableSha256, ResumableSha384, ResumableSha512, Sha1Digest,
+};
+use commoncrypto::hmac::CCHmacAlgorithm;
+use commoncrypto::pbkdf2::CCPseudoRandomAlgorithm;
+use commoncrypto::Error;
 use std::io::Write;
+use std::sync::Arc;
+use std::thread;
 
 const TO_HASH: &'static str = "The quick brown fox jumps over the lazy dog";
 const TO_HASH_MD5: &'static str = "9e107d9d372bb6826bd81d3542a419d6";
+const TO_HASH_MD5_BASE64: &str = "nhB9nTcrtoJr2B01QqQZ1g==";
+const TO_HASH_SHA1: &str = "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12";
+const TO_HASH_SHA1_BASE64: &str = "L9ThxnotKPzthJ7hu3bnORuT6xI=";
 
 #[test]
 fn md5_hasher() {
@@ -15,3 +43,946 @@ fn md5_hasher() {
     assert!(result.is_ok());
     assert_eq!(hex::encode(result.expect("Hash failed")), TO_HASH_MD5)
 }
+
+#[test]
+fn unsupported_algorithm() {
+    assert!(is_supported(CCDigestAlgorithm::kCCDigestSHA256));
+    assert!(!is_supported(CCDigestAlgorithm::kCCDigestNone));
+    assert_eq!(
+        Hasher::try_new(CCDigestAlgorithm::kCCDigestNone).unwrap_err(),
+        Error::Unimplemented
+    );
+    assert!(HashContext::try_new(CCDigestAlgorithm::kCCDigestNone).is_err());
+    assert!(Hasher::try_new(CCDigestAlgorithm::kCCDigestSHA256).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "CCDigestCreate failed for kCCDigestNone")]
+fn new_unsupported_algorithm() {
+    Hasher::new(CCDigestAlgorithm::kCCDigestNone);
+}
+
+const EMPTY_MD5: &str = "d41d8cd98f00b204e9800998ecf8427e";
+
+#[test]
+fn update_after_finish() {
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(b"discarded").is_ok());
+    hasher.finish().expect("Hash failed");
+    assert_eq!(
+        hex::encode(hasher.finish().expect("Hash failed")),
+        EMPTY_MD5
+    );
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    assert_eq!(
+        hex::encode(hasher.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+    hasher.finish().expect("Hash failed");
+    assert_eq!(hex::encode(hasher.peek().expect("Peek failed")), EMPTY_MD5);
+}
+
+#[test]
+fn hash_context() {
+    let context = HashContext::new(CCDigestAlgorithm::kCCDigestMD5)
+        .chain(&TO_HASH.as_bytes()[..10])
+        .and_then(|context| context.chain(&TO_HASH.as_bytes()[10..]))
+        .expect("Hash failed");
+    let mut forked = context.clone();
+    assert_eq!(
+        hex::encode(context.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+    assert_eq!(
+        hex::encode(forked.peek().expect("Peek failed")),
+        TO_HASH_MD5
+    );
+    let digest: Md5Digest = forked.finish_digest().expect("Hash failed");
+    assert_eq!(digest.to_string(), TO_HASH_MD5);
+}
+
+#[test]
+fn reset_hash_context() {
+    let mut context = HashContext::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(context.write_all(b"discarded").is_ok());
+    context.reset();
+    context.update(TO_HASH.as_bytes()).expect("Hash failed");
+    let mut out = [0u8; 16];
+    assert_eq!(context.finish_into(&mut out).expect("Hash failed"), 16);
+    assert_eq!(hex::encode(out), TO_HASH_MD5);
+}
+
+#[test]
+fn hash_context_finish_error() {
+    let context = HashContext::new(CCDigestAlgorithm::kCCDigestMD5)
+        .chain(TO_HASH.as_bytes())
+        .expect("Hash failed");
+
+    let mut out = [0u8; 15];
+    let err = context.finish_into(&mut out).unwrap_err();
+    assert_eq!(err.error(), Error::BufferTooSmall);
+    let context = err.into_context();
+
+    let err = context.finish_digest::<32>().unwrap_err();
+    assert_eq!(err.error(), Error::ParamError);
+    let digest: Md5Digest = err.into_context().finish_digest().expect("Hash failed");
+    assert_eq!(digest.to_string(), TO_HASH_MD5);
+}
+
+#[test]
+fn hash_context_from_finished_hasher() {
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    hasher.finish().expect("Hash failed");
+    let context = HashContext::from(hasher);
+    assert_eq!(
+        hex::encode(context.finish().expect("Hash failed")),
+        EMPTY_MD5
+    );
+
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    let context = HashContext::from(hasher);
+    assert_eq!(
+        hex::encode(context.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+}
+
+#[test]
+fn cloned_hasher() {
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(&TO_HASH.as_bytes()[..10]).is_ok());
+    let mut forked = hasher.clone();
+    assert!(hasher.write_all(&TO_HASH.as_bytes()[10..]).is_ok());
+    assert!(forked.write_all(&TO_HASH.as_bytes()[10..]).is_ok());
+    assert_eq!(
+        hex::encode(hasher.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+    assert_eq!(
+        hex::encode(forked.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+}
+
+#[test]
+fn peek_hasher() {
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    assert_eq!(
+        hex::encode(hasher.peek().expect("Peek failed")),
+        TO_HASH_MD5
+    );
+    assert_eq!(
+        hex::encode(hasher.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+}
+
+#[test]
+fn fixed_size_digest() {
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    assert_eq!(hasher.finish_digest::<32>(), Err(Error::ParamError));
+    let digest: Md5Digest = hasher.finish_digest().expect("Hash failed");
+    assert_eq!(digest.to_string(), TO_HASH_MD5);
+    assert_eq!(digest.as_ref(), &hex::decode(TO_HASH_MD5).unwrap()[..]);
+    assert_eq!(TO_HASH_MD5.parse::<Md5Digest>(), Ok(digest));
+}
+
+#[test]
+fn finish_into_buffer() {
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    let mut out = [0u8; 20];
+    assert_eq!(
+        hasher.finish_into(&mut out[..15]),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(hasher.finish_into(&mut out), Ok(16));
+    assert_eq!(hex::encode(&out[..16]), TO_HASH_MD5);
+    assert_eq!(out[16..], [0; 4]);
+}
+
+#[test]
+fn hasher_is_send() {
+    fn assert_send<T: Send>() {}
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send::<Hasher>();
+    assert_send::<HashContext>();
+    assert_send::<PooledHasher>();
+    assert_send_sync::<HasherPool>();
+
+    let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+    assert!(hasher.write_all(&TO_HASH.as_bytes()[..10]).is_ok());
+    let digest = thread::spawn(move || {
+        assert!(hasher.write_all(&TO_HASH.as_bytes()[10..]).is_ok());
+        hasher.finish().expect("Hash failed")
+    })
+    .join()
+    .unwrap();
+    assert_eq!(hex::encode(digest), TO_HASH_MD5);
+}
+
+#[test]
+fn hasher_pool() {
+    let pool = HasherPool::new(CCDigestAlgorithm::kCCDigestMD5, 1);
+    assert_eq!(pool.algorithm(), CCDigestAlgorithm::kCCDigestMD5);
+    {
+        let mut first = pool.get().expect("Pool failed");
+        let mut second = pool.get().expect("Pool failed");
+        assert!(first.write_all(TO_HASH.as_bytes()).is_ok());
+        assert!(second.write_all(b"discarded").is_ok());
+        assert_eq!(
+            hex::encode(first.finish().expect("Hash failed")),
+            TO_HASH_MD5
+        );
+    }
+    assert_eq!(pool.idle(), 1);
+    let mut recycled = pool.get().expect("Pool failed");
+    assert_eq!(pool.idle(), 0);
+    assert!(recycled.write_all(TO_HASH.as_bytes()).is_ok());
+    assert_eq!(
+        hex::encode(recycled.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+}
+
+#[test]
+fn hasher_pool_across_threads() {
+    let pool = Arc::new(HasherPool::new(CCDigestAlgorithm::kCCDigestMD5, 4));
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    let mut hasher = pool.get().expect("Pool failed");
+                    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+                    assert_eq!(
+                        hex::encode(hasher.finish().expect("Hash failed")),
+                        TO_HASH_MD5
+                    );
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert!(pool.idle() <= 4);
+}
+
+#[test]
+fn pooled_hasher_outlives_pool() {
+    let pool = HasherPool::new(CCDigestAlgorithm::kCCDigestMD5, 1);
+    let mut hasher = pool.get().expect("Pool failed");
+    let shared = pool.clone();
+    drop(pool);
+    let digest = thread::spawn(move || {
+        assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+        hasher.finish().expect("Hash failed")
+    })
+    .join()
+    .unwrap();
+    assert_eq!(hex::encode(digest), TO_HASH_MD5);
+    assert_eq!(shared.idle(), 1);
+}
+
+#[test]
+fn hasher_pool_unsupported_algorithm() {
+    let pool = HasherPool::new(CCDigestAlgorithm::kCCDigestNone, 1);
+    assert_eq!(pool.get().unwrap_err(), Error::Unimplemented);
+}
+
+macro_rules! test_resumable {
+    ($test_name: ident, $hasher: ident, $algorithm: ident) => {
+        #[test]
+        fn $test_name() {
+            let mut expected = Hasher::new(CCDigestAlgorithm::$algorithm);
+            assert!(expected.write_all(TO_HASH.as_bytes()).is_ok());
+            let expected = expected.finish().expect("Hash failed");
+            assert_eq!($hasher::ALGORITHM, CCDigestAlgorithm::$algorithm);
+            assert_eq!($hasher::LEN, expected.len());
+
+            let mut hasher = $hasher::new();
+            hasher.update(&TO_HASH.as_bytes()[..10]);
+            let state = hasher.export();
+            assert_eq!(state.algorithm(), CCDigestAlgorithm::$algorithm);
+            drop(hasher);
+
+            let state = HashState::from_bytes(state.as_bytes()).expect("Decode failed");
+            let mut resumed = $hasher::resume(&state).expect("Resume failed");
+            assert!(resumed.write_all(&TO_HASH.as_bytes()[10..]).is_ok());
+            assert_eq!(resumed.finish().as_ref(), &expected[..]);
+
+            // Across blocks, the chaining words are exported too
+            let data = TO_HASH.repeat(20);
+            let mut expected = Hasher::new(CCDigestAlgorithm::$algorithm);
+            assert!(expected.write_all(data.as_bytes()).is_ok());
+            let expected = expected.finish().expect("Hash failed");
+            let mut hasher = $hasher::new();
+            hasher.update(&data.as_bytes()[..300]);
+            let mut resumed = $hasher::resume(&hasher.export()).expect("Resume failed");
+            resumed.update(&data.as_bytes()[300..]);
+            assert_eq!(resumed.finish().as_ref(), &expected[..]);
+        }
+    };
+}
+
+test_resumable!(resumable_md5, ResumableMd5, kCCDigestMD5);
+test_resumable!(resumable_sha1, ResumableSha1, kCCDigestSHA1);
+test_resumable!(resumable_sha224, ResumableSha224, kCCDigestSHA224);
+test_resumable!(resumable_sha256, ResumableSha256, kCCDigestSHA256);
+test_resumable!(resumable_sha384, ResumableSha384, kCCDigestSHA384);
+test_resumable!(resumable_sha512, ResumableSha512, kCCDigestSHA512);
+
+#[test]
+fn malformed_hash_state() {
+    let mut hasher = ResumableSha256::new();
+    hasher.update(TO_HASH.as_bytes());
+    let state = hasher.export();
+    let bytes = state.as_bytes();
+    assert_eq!(&bytes[..5], b"CCHS\x01");
+    assert_eq!(
+        format!("{:?}", state),
+        "HashState { algorithm: kCCDigestSHA256, .. }"
+    );
+
+    assert_eq!(HashState::from_bytes(&bytes[..7]), Err(Error::DecodeError));
+    assert_eq!(
+        HashState::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::DecodeError)
+    );
+    for index in 0..6 {
+        let mut tampered = bytes.to_vec();
+        tampered[index] ^= 0x80;
+        assert_eq!(HashState::from_bytes(&tampered), Err(Error::DecodeError));
+    }
+    // The number of bytes hashed determines the length of the partial block
+    let mut tampered = bytes.to_vec();
+    tampered[13] ^= 0x01;
+    assert_eq!(HashState::from_bytes(&tampered), Err(Error::DecodeError));
+
+    let mut sha224 = bytes.to_vec();
+    sha224[5] = CCDigestAlgorithm::kCCDigestSHA224 as u8;
+    let sha224 = HashState::from_bytes(&sha224).expect("Decode failed");
+    assert!(ResumableSha224::resume(&sha224).is_ok());
+    assert_eq!(
+        ResumableSha256::resume(&sha224).unwrap_err(),
+        Error::ParamError
+    );
+    assert_eq!(
+        ResumableSha512::resume(&state).unwrap_err(),
+        Error::ParamError
+    );
+
+    // MD5 and SHA-1 contexts count bits in 64 bits
+    let mut md5 = ResumableMd5::new().export().as_bytes().to_vec();
+    md5[6] = 0x20;
+    assert_eq!(HashState::from_bytes(&md5), Err(Error::DecodeError));
+}
+
+#[test]
+fn hash_state_format() {
+    // SHA-256 of "abc" has not compressed a block yet, so its chaining words are the initial ones
+    let state = hex::decode(concat!(
+        "43434853010a",
+        "0000000000000003",
+        "6a09e667bb67ae853c6ef372a54ff53a510e527f9b05688c1f83d9ab5be0cd19",
+        "616263"
+    ))
+    .unwrap();
+    let mut hasher = ResumableSha256::new();
+    hasher.update(b"abc");
+    assert_eq!(hasher.export().as_bytes(), &state[..]);
+
+    let state = HashState::from_bytes(&state).expect("Decode failed");
+    let resumed = ResumableSha256::resume(&state).expect("Resume failed");
+    assert_eq!(
+        hex::encode(resumed.finish()),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_hash_state() {
+    let mut hasher = ResumableSha256::new();
+    hasher.update(TO_HASH.as_bytes());
+    let state = hasher.export();
+    let json = serde_json::to_string(&state).expect("Serialize failed");
+    let deserialized: HashState = serde_json::from_str(&json).expect("Deserialize failed");
+    assert_eq!(deserialized, state);
+    assert!(serde_json::from_str::<HashState>("[1, 2, 3]").is_err());
+}
+
+#[test]
+fn oneshot_hashes() {
+    use commoncrypto::hash::{md5, sha1, sha224, sha256, sha384, sha512};
+
+    let data = TO_HASH.as_bytes();
+    let oneshots: [(CCDigestAlgorithm, &[u8]); 6] = [
+        (CCDigestAlgorithm::kCCDigestMD5, &md5(data)),
+        (CCDigestAlgorithm::kCCDigestSHA1, &sha1(data)),
+        (CCDigestAlgorithm::kCCDigestSHA224, &sha224(data)),
+        (CCDigestAlgorithm::kCCDigestSHA256, &sha256(data)),
+        (CCDigestAlgorithm::kCCDigestSHA384, &sha384(data)),
+        (CCDigestAlgorithm::kCCDigestSHA512, &sha512(data)),
+    ];
+    for &(algorithm, digest) in oneshots.iter() {
+        let mut hasher = Hasher::new(algorithm);
+        assert!(hasher.write_all(data).is_ok());
+        assert_eq!(digest, &hasher.finish().expect("Hash failed")[..]);
+    }
+    assert_eq!(hex::encode(md5(data)), TO_HASH_MD5);
+}
+
+#[test]
+fn hasher_from_oid() {
+    let oid = CCDigestAlgorithm::kCCDigestMD5.oid().unwrap();
+    let mut hasher = Hasher::from_oid(oid).expect("Unknown OID");
+    assert_eq!(hasher.oid().as_deref(), Some(oid));
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    assert_eq!(
+        hex::encode(hasher.finish().expect("Hash failed")),
+        TO_HASH_MD5
+    );
+    assert_eq!(
+        Hasher::from_oid(b"\x06\x03\x2a\x03\x04").unwrap_err(),
+        Error::Unimplemented
+    );
+}
+
+#[test]
+fn pkcs1_digest_info() {
+    // Prefixes from RFC 8017, section 9.2, note 1
+    let prefixes = [
+        (
+            CCDigestAlgorithm::kCCDigestMD2,
+            "3020300c06082a864886f70d020205000410",
+        ),
+        (
+            CCDigestAlgorithm::kCCDigestMD5,
+            "3020300c06082a864886f70d020505000410",
+        ),
+        (
+            CCDigestAlgorithm::kCCDigestSHA1,
+            "3021300906052b0e03021a05000414",
+        ),
+        (
+            CCDigestAlgorithm::kCCDigestSHA224,
+            "302d300d06096086480165030402040500041c",
+        ),
+        (
+            CCDigestAlgorithm::kCCDigestSHA256,
+            "3031300d060960864801650304020105000420",
+        ),
+        (
+            CCDigestAlgorithm::kCCDigestSHA384,
+            "3041300d060960864801650304020205000430",
+        ),
+        (
+            CCDigestAlgorithm::kCCDigestSHA512,
+            "3051300d060960864801650304020305000440",
+        ),
+    ];
+    for &(algorithm, prefix) in prefixes.iter() {
+        let digest = vec![0xab; algorithm.output_len()];
+        let encoded = digest_info(algorithm, &digest).expect("Encoding failed");
+        assert_eq!(hex::encode(&encoded[..prefix.len() / 2]), prefix);
+        assert_eq!(&encoded[prefix.len() / 2..], &digest[..]);
+    }
+
+    assert_eq!(
+        digest_info(CCDigestAlgorithm::kCCDigestSHA256, &[0; 20]),
+        Err(Error::ParamError)
+    );
+    assert_eq!(
+        digest_info(CCDigestAlgorithm::kCCDigestRMD320, &[0; 40]),
+        Err(Error::Unimplemented)
+    );
+}
+
+#[test]
+fn digest_encoding() {
+    let md5: Md5Digest = TO_HASH_MD5.to_uppercase().parse().expect("valid hex");
+    assert_eq!(format!("{:X}", md5), TO_HASH_MD5.to_uppercase());
+    assert_eq!(md5.to_base64(), TO_HASH_MD5_BASE64);
+    assert_eq!(Md5Digest::from_base64(TO_HASH_MD5_BASE64), Ok(md5));
+
+    let sha1: Sha1Digest = TO_HASH_SHA1.parse().expect("valid hex");
+    assert_eq!(sha1.to_base64(), TO_HASH_SHA1_BASE64);
+    assert_eq!(Sha1Digest::from_base64(TO_HASH_SHA1_BASE64), Ok(sha1));
+
+    assert_eq!("9e10".parse::<Md5Digest>(), Err(Error::DecodeError));
+    assert_eq!(
+        TO_HASH_MD5.replace('e', "g").parse::<Md5Digest>(),
+        Err(Error::DecodeError)
+    );
+    assert_eq!(
+        Md5Digest::from_base64("nhB9nTcrtoJr2B01QqQZ1g="),
+        Err(Error::DecodeError)
+    );
+    assert_eq!(
+        Md5Digest::from_base64("nh==nTcrtoJr2B01QqQZ1g=="),
+        Err(Error::DecodeError)
+    );
+}
+
+#[test]
+fn digest_base64_trailing_bits() {
+    // The last character differs from TO_HASH_MD5_BASE64 only in bits which are not decoded
+    assert_eq!(
+        Md5Digest::from_base64("nhB9nTcrtoJr2B01QqQZ1h=="),
+        Err(Error::DecodeError)
+    );
+}
+
+#[test]
+fn digest_equality() {
+    let digest = Digest::from([1u8, 2, 3, 4]);
+    assert_eq!(digest, Digest::from([1, 2, 3, 4]));
+    assert_ne!(digest, Digest::from([1, 2, 3, 5]));
+    assert_eq!(<[u8; 4]>::from(digest), [1, 2, 3, 4]);
+}
+
+#[cfg(feature = "digest")]
+mod digest_traits {
+    use super::{TO_HASH, TO_HASH_MD5, TO_HASH_SHA1};
+    use commoncrypto::hash::{Md5, Sha1, Sha224, Sha256, Sha384, Sha512};
+
+    const TO_HASH_SHA224: &str = "730e109bd7a8a32b1cb9d9a09aa2325d2430587ddbc0c38bad911525";
+    const TO_HASH_SHA256: &str = concat!(
+        "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb76",
+        "2d02d0bf37c9e592"
+    );
+    const TO_HASH_SHA384: &str = concat!(
+        "ca737f1014a48f4c0b6dd43cb177b0afd9e5169367544c494011e3317dbf9a509cb1e5dc1e85a941bbee",
+        "3d7f2afbc9b1"
+    );
+    const TO_HASH_SHA512: &str = concat!(
+        "07e547d9586f6a73f73fbac0435ed76951218fb7d0c8d788a309d785436bbb642e93a252a954f2391254",
+        "7d1e8a3b5ed6e1bfd7097821233fa0538f3db854fee6"
+    );
+
+    macro_rules! test_digest {
+        ($test_name: ident, $hasher: ident, $expected_hash: ident) => {
+            #[test]
+            fn $test_name() {
+                use digest::Digest;
+
+                assert_eq!(hex::encode($hasher::digest(TO_HASH)), $expected_hash);
+
+                let mut hasher = $hasher::new();
+                hasher.update(&TO_HASH[..10]);
+                let mut forked = hasher.clone();
+                hasher.update(&TO_HASH[10..]);
+                forked.update(&TO_HASH[10..]);
+                assert_eq!(hex::encode(hasher.finalize_reset()), $expected_hash);
+                assert_eq!(hex::encode(forked.finalize()), $expected_hash);
+
+                hasher.update(TO_HASH);
+                assert_eq!(hex::encode(hasher.finalize()), $expected_hash);
+            }
+        };
+    }
+
+    test_digest!(md5_digest, Md5, TO_HASH_MD5);
+    test_digest!(sha1_digest, Sha1, TO_HASH_SHA1);
+    test_digest!(sha224_digest, Sha224, TO_HASH_SHA224);
+    test_digest!(sha256_digest, Sha256, TO_HASH_SHA256);
+    test_digest!(sha384_digest, Sha384, TO_HASH_SHA384);
+    test_digest!(sha512_digest, Sha512, TO_HASH_SHA512);
+
+    #[test]
+    fn dyn_digest() {
+        use digest::DynDigest;
+
+        let mut hashers: Vec<Box<dyn DynDigest>> =
+            vec![Box::new(Md5::default()), Box::new(Sha256::default())];
+        let mut results = Vec::new();
+        for hasher in &mut hashers {
+            hasher.update(TO_HASH.as_bytes());
+            let mut result = vec![0; hasher.output_size()];
+            hasher
+                .finalize_into_reset(&mut result)
+                .expect("buffer is output_size");
+            results.push(hex::encode(result));
+        }
+        assert_eq!(results, vec![TO_HASH_MD5, TO_HASH_SHA256]);
+    }
+
+    #[test]
+    fn hmac_crate() {
+        use hmac::{Mac, SimpleHmac};
+
+        // RFC 4231, test case 2
+        let mut mac = SimpleHmac::<Sha256>::new_from_slice(b"Jefe").expect("any key length");
+        mac.update(b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(mac.finalize().into_bytes()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
+
+#[test]
+fn hashing_reader() {
+    use commoncrypto::hash::HashingReader;
+    use std::io::{Cursor, Read};
+
+    let mut reader = HashingReader::new(
+        CCDigestAlgorithm::kCCDigestMD5,
+        Cursor::new(TO_HASH.as_bytes()),
+    );
+    let mut passed_through = String::new();
+    assert!(reader.read_to_string(&mut passed_through).is_ok());
+    let (inner, digest) = reader.finish().expect("Hash failed");
+    assert_eq!(passed_through, TO_HASH);
+    assert_eq!(inner.position(), TO_HASH.len() as u64);
+    assert_eq!(hex::encode(digest), TO_HASH_MD5);
+}
+
+#[test]
+fn hashing_writer() {
+    use commoncrypto::hash::HashingWriter;
+
+    let mut writer = HashingWriter::new(CCDigestAlgorithm::kCCDigestMD5, Vec::new());
+    assert!(writer.write_all(TO_HASH.as_bytes()).is_ok());
+    let (inner, digest) = writer.finish().expect("Hash failed");
+    assert_eq!(inner, TO_HASH.as_bytes());
+    assert_eq!(hex::encode(digest), TO_HASH_MD5);
+}
+
+#[test]
+fn hash_from_reader() {
+    use commoncrypto::hash::hash_reader;
+
+    let digest = hash_reader(CCDigestAlgorithm::kCCDigestMD5, TO_HASH.as_bytes());
+    assert_eq!(hex::encode(digest.expect("Hash failed")), TO_HASH_MD5);
+}
+
+#[cfg(any(feature = "tokio", feature = "futures-io"))]
+mod async_adapters {
+    use std::io;
+    use std::task::{Context, Poll, Waker};
+
+    /// Reader which returns `Poll::Pending` before each chunk of at most 7 bytes.
+    struct PendingReader {
+        data: &'static [u8],
+        ready: bool,
+    }
+
+    impl PendingReader {
+        fn new(data: &'static [u8]) -> PendingReader {
+            PendingReader { data, ready: false }
+        }
+
+        fn poll_chunk(&mut self, cx: &mut Context<'_>, len: usize) -> Poll<&'static [u8]> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            let (chunk, rest) = self.data.split_at(len.min(7).min(self.data.len()));
+            self.data = rest;
+            Poll::Ready(chunk)
+        }
+    }
+
+    /// Writer which returns `Poll::Pending` before accepting each chunk of at most 5 bytes.
+    #[derive(Default)]
+    struct PendingWriter {
+        data: Vec<u8>,
+        ready: bool,
+    }
+
+    impl PendingWriter {
+        fn poll_chunk(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            let len = buf.len().min(5);
+            self.data.extend_from_slice(&buf[..len]);
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    /// Polls `poll` until it is ready, counting the number of times it was pending.
+    fn block_on<T, F>(mut poll: F) -> (T, usize)
+    where
+        F: FnMut(&mut Context<'_>) -> Poll<T>,
+    {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut pending = 0;
+        loop {
+            match poll(&mut cx) {
+                Poll::Ready(value) => return (value, pending),
+                Poll::Pending => pending += 1,
+            }
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    mod tokio_traits {
+        use super::super::{TO_HASH, TO_HASH_MD5};
+        use super::{block_on, PendingReader, PendingWriter};
+        use commoncrypto::hash::{CCDigestAlgorithm, HashingReader, HashingWriter};
+        use std::io;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+        use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+        impl AsyncRead for PendingReader {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut ReadBuf<'_>,
+            ) -> Poll<io::Result<()>> {
+                let remaining = buf.remaining();
+                self.get_mut().poll_chunk(cx, remaining).map(|chunk| {
+                    buf.put_slice(chunk);
+                    Ok(())
+                })
+            }
+        }
+
+        impl AsyncWrite for PendingWriter {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                self.get_mut().poll_chunk(cx, buf)
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        #[test]
+        fn tokio_hashing_reader() {
+            let inner = PendingReader::new(TO_HASH.as_bytes());
+            let mut reader = HashingReader::new(CCDigestAlgorithm::kCCDigestMD5, inner);
+            let mut read = Vec::new();
+            loop {
+                let mut buf = [0u8; 16];
+                let mut buf = ReadBuf::new(&mut buf);
+                let (result, pending) =
+                    block_on(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf));
+                assert!(result.is_ok());
+                assert_eq!(pending, 1);
+                if buf.filled().is_empty() {
+                    break;
+                }
+                read.extend_from_slice(buf.filled());
+            }
+            assert_eq!(read, TO_HASH.as_bytes());
+            let (_, digest) = reader.finish().expect("Hash failed");
+            assert_eq!(hex::encode(digest), TO_HASH_MD5);
+        }
+
+        #[test]
+        fn tokio_hashing_writer() {
+            let inner = PendingWriter::default();
+            let mut writer = HashingWriter::new(CCDigestAlgorithm::kCCDigestMD5, inner);
+            let mut data = TO_HASH.as_bytes();
+            while !data.is_empty() {
+                let (result, pending) = block_on(|cx| Pin::new(&mut writer).poll_write(cx, data));
+                assert_eq!(pending, 1);
+                data = &data[result.expect("Write failed")..];
+            }
+            let (result, _) = block_on(|cx| Pin::new(&mut writer).poll_shutdown(cx));
+            assert!(result.is_ok());
+            let (inner, digest) = writer.finish().expect("Hash failed");
+            assert_eq!(inner.data, TO_HASH.as_bytes());
+            assert_eq!(hex::encode(digest), TO_HASH_MD5);
+        }
+    }
+
+    #[cfg(feature = "futures-io")]
+    mod futures_io_traits {
+        use super::super::{TO_HASH, TO_HASH_MD5};
+        use super::{block_on, PendingReader, PendingWriter};
+        use commoncrypto::hash::{CCDigestAlgorithm, HashingReader, HashingWriter};
+        use futures_io::{AsyncRead, AsyncWrite};
+        use std::io;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        impl AsyncRead for PendingReader {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                self.get_mut().poll_chunk(cx, buf.len()).map(|chunk| {
+                    buf[..chunk.len()].copy_from_slice(chunk);
+                    Ok(chunk.len())
+                })
+            }
+        }
+
+        impl AsyncWrite for PendingWriter {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                self.get_mut().poll_chunk(cx, buf)
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        #[test]
+        fn futures_io_hashing_reader() {
+            let inner = PendingReader::new(TO_HASH.as_bytes());
+            let mut reader = HashingReader::new(CCDigestAlgorithm::kCCDigestMD5, inner);
+            let mut read = Vec::new();
+            loop {
+                let mut buf = [0u8; 16];
+                let (result, pending) =
+                    block_on(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf));
+                assert_eq!(pending, 1);
+                match result.expect("Read failed") {
+                    0 => break,
+                    len => read.extend_from_slice(&buf[..len]),
+                }
+            }
+            assert_eq!(read, TO_HASH.as_bytes());
+            let (_, digest) = reader.finish().expect("Hash failed");
+            assert_eq!(hex::encode(digest), TO_HASH_MD5);
+        }
+
+        #[test]
+        fn futures_io_hashing_writer() {
+            let inner = PendingWriter::default();
+            let mut writer = HashingWriter::new(CCDigestAlgorithm::kCCDigestMD5, inner);
+            let mut data = TO_HASH.as_bytes();
+            while !data.is_empty() {
+                let (result, pending) = block_on(|cx| Pin::new(&mut writer).poll_write(cx, data));
+                assert_eq!(pending, 1);
+                data = &data[result.expect("Write failed")..];
+            }
+            let (result, _) = block_on(|cx| Pin::new(&mut writer).poll_close(cx));
+            assert!(result.is_ok());
+            let (inner, digest) = writer.finish().expect("Hash failed");
+            assert_eq!(inner.data, TO_HASH.as_bytes());
+            assert_eq!(hex::encode(digest), TO_HASH_MD5);
+        }
+    }
+}
+
+#[test]
+fn supported_algorithms() {
+    let supported: Vec<Algorithm> = algorithms().collect();
+    let sha256 = Algorithm::new(CCDigestAlgorithm::kCCDigestSHA256).expect("SHA-256 unsupported");
+    assert!(supported.contains(&sha256));
+    assert!(supported
+        .iter()
+        .all(|algorithm| is_supported(algorithm.digest_algorithm())));
+    assert_eq!(sha256.output_len(), 32);
+    assert_eq!(sha256.block_len(), 64);
+    assert_eq!(sha256.oid(), CCDigestAlgorithm::kCCDigestSHA256.oid());
+    assert_eq!(
+        sha256.hmac_algorithm(),
+        Some(CCHmacAlgorithm::kCCHmacAlgSHA256)
+    );
+    assert_eq!(
+        sha256.prf(),
+        Some(CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256)
+    );
+    assert_eq!(
+        Algorithm::new(CCDigestAlgorithm::kCCDigestNone).unwrap_err(),
+        Error::Unimplemented
+    );
+
+    let sha512: Algorithm = "sha-512".parse().expect("Parse failed");
+    assert_eq!(sha512.block_len(), 128);
+    assert_eq!(sha512.to_string(), "SHA-512");
+    let mut hasher = sha512.hasher().expect("Hasher failed");
+    assert!(hasher.write_all(TO_HASH.as_bytes()).is_ok());
+    assert_eq!(hasher.finish().expect("Hash failed").len(), 64);
+    assert_eq!(
+        "whirlpool".parse::<Algorithm>().unwrap_err(),
+        Error::DecodeError
+    );
+}
+
+#[cfg(not(target_vendor = "apple"))]
+mod injected_failures {
+    use super::{TO_HASH, TO_HASH_MD5};
+    use commoncrypto::hash::{CCDigestAlgorithm, Hasher, HasherPool};
+    use commoncrypto::Error;
+    use commoncrypto_shim::{clear_failures, inject_failure};
+    use commoncrypto_sys::CCCryptorStatus;
+    use std::os::raw::c_int;
+
+    #[test]
+    fn create_failure() {
+        inject_failure("CCDigestCreate", 0);
+        assert_eq!(
+            Hasher::try_new(CCDigestAlgorithm::kCCDigestSHA256).unwrap_err(),
+            Error::MemoryFailure
+        );
+        let pool = HasherPool::new(CCDigestAlgorithm::kCCDigestSHA256, 1);
+        assert_eq!(pool.get().unwrap_err(), Error::MemoryFailure);
+        clear_failures();
+        assert!(pool.get().is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "CCDigestCreate failed for kCCDigestSHA256: ")]
+    fn new_panics_on_create_failure() {
+        inject_failure("CCDigestCreate", 0);
+        Hasher::new(CCDigestAlgorithm::kCCDigestSHA256);
+    }
+
+    #[test]
+    #[should_panic(expected = "CCDigestClone failed")]
+    fn clone_panics_on_failure() {
+        let hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+        inject_failure("CCDigestClone", 0);
+        let _ = hasher.clone();
+    }
+
+    #[test]
+    fn update_and_finish_failures() {
+        let mut hasher = Hasher::new(CCDigestAlgorithm::kCCDigestMD5);
+        inject_failure("CCDigestUpdate", CCCryptorStatus::kCCParamError as c_int);
+        assert_eq!(hasher.update(TO_HASH.as_bytes()), Err(Error::ParamError));
+        clear_failures();
+        assert!(hasher.update(TO_HASH.as_bytes()).is_ok());
+
+        inject_failure(
+            "CCDigestGetDigest",
+            CCCryptorStatus::kCCMemoryFailure as c_int,
+        );
+        assert_eq!(hasher.peek(), Err(Error::MemoryFailure));
+        inject_failure("CCDigestFinal", CCCryptorStatus::kCCMemoryFailure as c_int);
+        assert_eq!(hasher.finish(), Err(Error::MemoryFailure));
+        clear_failures();
+        // The failed calls left the digest unfinished
+        assert_eq!(
+            hex::encode(hasher.finish().expect("Hash failed")),
+            TO_HASH_MD5
+        );
+    }
+}
@@ -0,0 +1,106 @@
+extern crate commoncrypto;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+extern crate hex;
+
+use commoncrypto::hmac::{hmac, CCHmacAlgorithm, Hmac};
+use std::io::Write;
+
+// These keys, data and MACs come from the test vectors stated in RFC 2202 and RFC 4231
+const KEY: &str = "Jefe";
+const DATA: &str = "what do ya want for nothing?";
+const MAC_MD5: &str = "750c783e6ab0b503eaa86e310a5db738";
+const MAC_SHA1: &str = "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79";
+const MAC_SHA224: &str = "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44";
+const MAC_SHA256: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+const MAC_SHA384: &str = concat!(
+    "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e",
+    "8e2240ca5e69e2c78b3239ecfab21649"
+);
+const MAC_SHA512: &str = concat!(
+    "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554",
+    "9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+);
+
+macro_rules! test_hmac {
+    (
+        $test_name: ident,
+        $algorithm: ident,
+        $expected_mac: ident
+    ) => {
+        #[test]
+        fn $test_name() {
+            let mut mac = Hmac::new(CCHmacAlgorithm::$algorithm, KEY.as_bytes());
+            assert!(mac.write_all(DATA.as_bytes()).is_ok());
+            assert_eq!(hex::encode(mac.finish()), $expected_mac);
+            assert_eq!(
+                hex::encode(hmac(
+                    CCHmacAlgorithm::$algorithm,
+                    KEY.as_bytes(),
+                    DATA.as_bytes()
+                )),
+                $expected_mac
+            );
+        }
+    };
+}
+
+test_hmac!(md5_hmac, kCCHmacAlgMD5, MAC_MD5);
+test_hmac!(sha1_hmac, kCCHmacAlgSHA1, MAC_SHA1);
+test_hmac!(sha224_hmac, kCCHmacAlgSHA224, MAC_SHA224);
+test_hmac!(sha256_hmac, kCCHmacAlgSHA256, MAC_SHA256);
+test_hmac!(sha384_hmac, kCCHmacAlgSHA384, MAC_SHA384);
+test_hmac!(sha512_hmac, kCCHmacAlgSHA512, MAC_SHA512);
+
+#[test]
+fn hmac_reuse_after_finish() {
+    let mut mac = Hmac::new(CCHmacAlgorithm::kCCHmacAlgSHA256, KEY.as_bytes());
+    for _ in 0..2 {
+        mac.update(DATA.as_bytes());
+        assert_eq!(hex::encode(mac.finish()), MAC_SHA256);
+    }
+}
+
+#[test]
+fn hmac_verify() {
+    let expected = hex::decode(MAC_SHA256).unwrap();
+    let mut mac = Hmac::new(CCHmacAlgorithm::kCCHmacAlgSHA256, KEY.as_bytes());
+    mac.update(DATA.as_bytes());
+    assert!(mac.verify(&expected));
+
+    let mut tampered = expected.clone();
+    tampered[0] ^= 1;
+    mac.update(DATA.as_bytes());
+    assert!(!mac.verify(&tampered));
+
+    mac.update(DATA.as_bytes());
+    assert!(!mac.verify(&expected[..16]));
+}
+
+#[cfg(not(target_vendor = "apple"))]
+mod injected_failures {
+    use super::{DATA, KEY, MAC_SHA256};
+    use commoncrypto::hmac::{hmac, CCHmacAlgorithm, Hmac};
+    use commoncrypto_shim::{clear_failures, inject_failure};
+
+    // The CCHmac functions return nothing, so they cannot report a failure, and the wrappers have
+    // no error path
+    #[test]
+    fn hmac_cannot_fail() {
+        for function in ["CCHmac", "CCHmacInit", "CCHmacUpdate", "CCHmacFinal"].iter() {
+            inject_failure(function, -1);
+        }
+        let mut mac = Hmac::new(CCHmacAlgorithm::kCCHmacAlgSHA256, KEY.as_bytes());
+        mac.update(DATA.as_bytes());
+        assert_eq!(hex::encode(mac.finish()), MAC_SHA256);
+        assert_eq!(
+            hex::encode(hmac(
+                CCHmacAlgorithm::kCCHmacAlgSHA256,
+                KEY.as_bytes(),
+                DATA.as_bytes()
+            )),
+            MAC_SHA256
+        );
+        clear_failures();
+    }
+}
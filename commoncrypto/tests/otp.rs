@@ -0,0 +1,243 @@
+extern crate commoncrypto;
+
+use commoncrypto::hmac::CCHmacAlgorithm;
+use commoncrypto::otp::{Hotp, Otp, OtpAuthUri, Totp};
+use commoncrypto::Error;
+use std::time::{Duration, UNIX_EPOCH};
+
+const SHA1_SECRET: &[u8] = b"12345678901234567890";
+const SHA256_SECRET: &[u8] = b"12345678901234567890123456789012";
+const SHA512_SECRET: &[u8] = b"1234567890123456789012345678901234567890123456789012345678901234";
+
+#[test]
+fn hotp_rfc4226() {
+    // RFC 4226, appendix D
+    let expected = [
+        "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871",
+        "520489",
+    ];
+    let hotp = Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 6).unwrap();
+    for (counter, code) in expected.iter().enumerate() {
+        assert_eq!(hotp.generate(counter as u64), *code);
+    }
+}
+
+#[test]
+fn hotp_verify() {
+    let hotp = Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 6).unwrap();
+    assert_eq!(hotp.verify("969429", 3, 0), Ok(Some(3)));
+    assert_eq!(hotp.verify("969429", 1, 2), Ok(Some(3)));
+    assert_eq!(hotp.verify("969429", 1, 1), Ok(None));
+    assert_eq!(hotp.verify("969429", 4, 5), Ok(None));
+    assert_eq!(hotp.verify("96942", 3, 0), Ok(None));
+    assert_eq!(hotp.verify("969429", 0, Hotp::MAX_LOOK_AHEAD), Ok(Some(3)));
+    assert_eq!(
+        hotp.verify("969429", 0, u64::MAX).unwrap_err(),
+        Error::ParamError
+    );
+}
+
+#[test]
+fn invalid_hotp_params() {
+    assert_eq!(
+        Hotp::new(CCHmacAlgorithm::kCCHmacAlgMD5, SHA1_SECRET, 6).unwrap_err(),
+        Error::ParamError
+    );
+    assert_eq!(
+        Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 5).unwrap_err(),
+        Error::ParamError
+    );
+    assert_eq!(
+        Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 11).unwrap_err(),
+        Error::ParamError
+    );
+    // RFC 4226, section 4: the secret is at least 128 bits long
+    assert_eq!(
+        Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, &SHA1_SECRET[..15], 6).unwrap_err(),
+        Error::ParamError
+    );
+    assert_eq!(
+        Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, &SHA1_SECRET[..15], 6).unwrap_err(),
+        Error::ParamError
+    );
+    assert!(Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, &SHA1_SECRET[..16], 6).is_ok());
+}
+
+#[test]
+fn totp_rfc6238() {
+    // RFC 6238, appendix B
+    let vectors = [
+        (59, "94287082", "46119246", "90693936"),
+        (1111111109, "07081804", "68084774", "25091201"),
+        (1111111111, "14050471", "67062674", "99943326"),
+        (1234567890, "89005924", "91819424", "93441116"),
+        (2000000000, "69279037", "90698825", "38618901"),
+        (20000000000, "65353130", "77737706", "47863826"),
+    ];
+    let sha1 = Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 8).unwrap();
+    let sha256 = Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA256, SHA256_SECRET, 8).unwrap();
+    let sha512 = Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA512, SHA512_SECRET, 8).unwrap();
+    for &(secs, sha1_code, sha256_code, sha512_code) in vectors.iter() {
+        let time = UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(sha1.generate_at(time).unwrap(), sha1_code);
+        assert_eq!(sha256.generate_at(time).unwrap(), sha256_code);
+        assert_eq!(sha512.generate_at(time).unwrap(), sha512_code);
+    }
+}
+
+#[test]
+fn totp_verify_with_skew() {
+    let totp = Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 8).unwrap();
+    // 1111111109 and 1111111111 fall in adjacent steps
+    let time = UNIX_EPOCH + Duration::from_secs(1111111111);
+    assert_eq!(totp.verify_at("14050471", time), Some(37037037));
+    assert_eq!(totp.verify_at("07081804", time), Some(37037036));
+    assert_eq!(
+        totp.clone().with_skew(Totp::MAX_SKEW + 1).unwrap_err(),
+        Error::ParamError
+    );
+    let strict = totp.with_skew(0).unwrap();
+    assert_eq!(strict.verify_at("07081804", time), None);
+    assert_eq!(strict.verify_at("14050471", time), Some(37037037));
+
+    let totp = Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 6).unwrap();
+    let code = totp.generate().unwrap();
+    assert!(totp.verify(&code).is_some());
+}
+
+#[test]
+fn totp_step() {
+    let totp = Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 6)
+        .unwrap()
+        .with_step(Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(totp.step(), Duration::from_secs(60));
+    assert_eq!(totp.time_step(UNIX_EPOCH + Duration::from_secs(119)), Ok(1));
+    assert_eq!(
+        totp.time_step(UNIX_EPOCH - Duration::from_secs(1)),
+        Err(Error::ParamError)
+    );
+    assert_eq!(
+        totp.clone()
+            .with_step(Duration::from_millis(1500))
+            .unwrap_err(),
+        Error::ParamError
+    );
+    assert_eq!(
+        totp.with_step(Duration::from_secs(0)).unwrap_err(),
+        Error::ParamError
+    );
+}
+
+#[test]
+fn parse_otpauth_uri() {
+    let uri = OtpAuthUri::parse(
+        "otpauth://totp/ACME%20Co:john.doe@email.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+         &issuer=ACME+Co&algorithm=SHA256&digits=8&period=60",
+    )
+    .unwrap();
+    assert_eq!(uri.label, "ACME Co:john.doe@email.com");
+    assert_eq!(uri.issuer.as_deref(), Some("ACME Co"));
+    match uri.otp {
+        Otp::Totp(ref totp) => {
+            assert_eq!(totp.hotp().secret(), SHA1_SECRET);
+            assert_eq!(totp.hotp().algorithm(), CCHmacAlgorithm::kCCHmacAlgSHA256);
+            assert_eq!(totp.hotp().digits(), 8);
+            assert_eq!(totp.step(), Duration::from_secs(60));
+        }
+        Otp::Hotp(..) => panic!("Expected TOTP"),
+    }
+
+    let uri =
+        OtpAuthUri::parse("otpauth://hotp/alice?secret=jbswy3dpehpk3pxpjbswy3dpehpk3pxp&counter=7")
+            .unwrap();
+    assert_eq!(uri.issuer, None);
+    match uri.otp {
+        Otp::Hotp(ref hotp, counter) => {
+            assert_eq!(
+                hotp.secret(),
+                b"Hello!\xde\xad\xbe\xefHello!\xde\xad\xbe\xef"
+            );
+            assert_eq!(hotp.algorithm(), CCHmacAlgorithm::kCCHmacAlgSHA1);
+            assert_eq!(hotp.digits(), 6);
+            assert_eq!(counter, 7);
+        }
+        Otp::Totp(_) => panic!("Expected HOTP"),
+    }
+}
+
+#[test]
+fn malformed_otpauth_uri() {
+    let malformed = [
+        "https://totp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP",
+        "otpauth://totp",
+        "otpauth:/é",
+        "otpauth:é/totp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP",
+        "otpauth://motp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP",
+        "otpauth://totp/alice",
+        "otpauth://totp/alice?secret=JBSWY3DPEHPK3PX1",
+        "otpauth://totp/alice?secret=AF",
+        "otpauth://totp/alice?secret=AEA",
+        "otpauth://totp/alice%2?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP",
+        "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&digits=six",
+        "otpauth://hotp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP",
+    ];
+    for uri in malformed.iter() {
+        assert_eq!(
+            OtpAuthUri::parse(uri).unwrap_err(),
+            Error::DecodeError,
+            "{}",
+            uri
+        );
+    }
+    assert_eq!(
+        OtpAuthUri::parse(
+            "otpauth://totp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&algorithm=RIPEMD160"
+        )
+        .unwrap_err(),
+        Error::Unimplemented
+    );
+    assert_eq!(
+        OtpAuthUri::parse("otpauth://totp/alice?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&digits=4")
+            .unwrap_err(),
+        Error::ParamError
+    );
+    assert_eq!(
+        OtpAuthUri::parse("otpauth://totp/alice?secret=JBSWY3DPEHPK3PXP").unwrap_err(),
+        Error::ParamError
+    );
+}
+
+#[test]
+fn otpauth_uri_round_trip() {
+    let uri = OtpAuthUri {
+        otp: Otp::Totp(Totp::new(CCHmacAlgorithm::kCCHmacAlgSHA512, SHA512_SECRET, 6).unwrap()),
+        label: "Example:alice@example.com".to_string(),
+        issuer: Some("Example".to_string()),
+    };
+    let encoded = uri.to_uri();
+    assert!(encoded.starts_with("otpauth://totp/Example%3Aalice%40example.com?secret=GEZDGNBV"));
+    assert!(encoded.ends_with("&issuer=Example&algorithm=SHA512&digits=6&period=30"));
+    assert_eq!(OtpAuthUri::parse(&encoded).unwrap(), uri);
+
+    let uri = OtpAuthUri {
+        otp: Otp::Hotp(
+            Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 6).unwrap(),
+            42,
+        ),
+        label: "bob".to_string(),
+        issuer: None,
+    };
+    let encoded = uri.to_uri();
+    assert_eq!(
+        encoded,
+        "otpauth://hotp/bob?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&algorithm=SHA1&digits=6&counter=42"
+    );
+    assert_eq!(OtpAuthUri::parse(&encoded).unwrap(), uri);
+}
+
+#[test]
+fn otp_debug_redacts_secret() {
+    let hotp = Hotp::new(CCHmacAlgorithm::kCCHmacAlgSHA1, SHA1_SECRET, 6).unwrap();
+    assert!(!format!("{:?}", hotp).contains("49, 50"));
+}
@@ -1,7 +1,30 @@
 extern crate commoncrypto;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_shim;
+#[cfg(not(target_vendor = "apple"))]
+extern crate commoncrypto_sys;
 extern crate hex;
+#[cfg(feature = "password-hash")]
+extern crate password_hash;
 
-use commoncrypto::pbkdf2::{pbkdf2, CCPseudoRandomAlgorithm};
+use commoncrypto::pbkdf2::{
+    calibrate, hash_password, needs_rehash, pbkdf2, pbkdf2_into, verify_password,
+    CCPseudoRandomAlgorithm, Pbkdf2Params,
+};
+use commoncrypto::Error;
+use std::time::Duration;
+
+// Generated with Python's hashlib.pbkdf2_hmac
+const PHC_SHA256: &str =
+    "$pbkdf2-sha256$i=1000,l=32$c2FsdHNhbHRzYWx0c2FsdA$8nX7hwFEzIB8aPajJTYK8weHQc5Ngz0pFVAKvSu4jQA";
+const PHC_SHA512: &str = "$pbkdf2-sha512$i=1000$c2FsdHNhbHRzYWx0c2FsdA$715rqIr5dXOVPpBhqqsugl037zT5bWJTWYmZtIcK8hBnisKpwfY7kokvwjDrNHqHhF50Pb7MD6HvkJwiDQw4ww";
+
+fn fast_params() -> Pbkdf2Params {
+    Pbkdf2Params {
+        rounds: 1000,
+        ..Pbkdf2Params::default()
+    }
+}
 
 #[test]
 fn derive_pbkdf2() {
@@ -18,3 +41,184 @@ fn derive_pbkdf2() {
         hex::encode(derived)
     );
 }
+
+#[test]
+fn derive_pbkdf2_empty_key() {
+    let result = pbkdf2(
+        b"password",
+        b"salt",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1,
+        1,
+        0,
+    );
+    assert_eq!(result.unwrap_err(), Error::ParamError);
+    let result = pbkdf2_into(
+        b"password",
+        b"salt",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1,
+        1,
+        &mut [],
+    );
+    assert_eq!(result, Err(Error::ParamError));
+}
+
+#[test]
+fn derive_pbkdf2_into() {
+    let mut derived = [0u8; 24];
+    let prf = CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1;
+    pbkdf2_into(b"password", b"salt", prf, 1, &mut derived[..20]).unwrap();
+    assert_eq!(
+        "0c60c80f961f0e71f3a9b524af6012062fe037a600000000",
+        hex::encode(derived)
+    );
+}
+
+#[test]
+fn calibrate_rounds() {
+    let params = Pbkdf2Params::calibrated(
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256,
+        8,
+        16,
+        Duration::from_millis(20),
+        32,
+    )
+    .unwrap();
+    assert!(params.rounds > 0);
+    assert_eq!(
+        params
+            .derive(b"password", b"saltsaltsaltsalt")
+            .unwrap()
+            .len(),
+        32
+    );
+}
+
+#[test]
+fn calibrate_empty_key() {
+    let result = calibrate(
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256,
+        8,
+        16,
+        Duration::from_millis(20),
+        0,
+    );
+    assert_eq!(result.unwrap_err(), Error::ParamError);
+    let result = pbkdf2_into(
+        b"password",
+        b"salt",
+        CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA1,
+        1,
+        &mut [],
+    );
+    assert_eq!(result, Err(Error::ParamError));
+}
+
+#[test]
+fn verify_phc_string() {
+    assert!(verify_password(b"password", PHC_SHA256).unwrap());
+    assert!(!verify_password(b"passw0rd", PHC_SHA256).unwrap());
+    assert!(verify_password(b"password", PHC_SHA512).unwrap());
+}
+
+#[test]
+fn verify_malformed_phc_string() {
+    for phc in &[
+        "",
+        "$pbkdf2-sha256$i=1000$c2FsdA",
+        "$scrypt$ln=16,r=8,p=1$c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$l=4$c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$i=1000,l=5$c2FsdA$aGFzaA",
+        "$pbkdf2-sha256$i=1000$c2FsdA$aGFzaA==",
+    ] {
+        assert_eq!(verify_password(b"password", phc), Err(Error::DecodeError));
+    }
+}
+
+#[test]
+fn hash_and_verify_password() {
+    let phc = hash_password(b"password", &fast_params()).unwrap();
+    assert!(phc.starts_with("$pbkdf2-sha256$i=1000,l=32$"));
+    assert!(verify_password(b"password", &phc).unwrap());
+    assert!(!verify_password(b"passw0rd", &phc).unwrap());
+    assert_ne!(phc, hash_password(b"password", &fast_params()).unwrap());
+}
+
+#[test]
+fn phc_needs_rehash() {
+    let params = fast_params();
+    assert!(!needs_rehash(PHC_SHA256, &params).unwrap());
+    assert!(needs_rehash(PHC_SHA512, &params).unwrap());
+    let stronger = Pbkdf2Params {
+        rounds: 2000,
+        ..params
+    };
+    assert!(needs_rehash(PHC_SHA256, &stronger).unwrap());
+    let weaker = Pbkdf2Params {
+        rounds: 500,
+        ..params
+    };
+    assert!(!needs_rehash(PHC_SHA256, &weaker).unwrap());
+}
+
+#[cfg(feature = "password-hash")]
+#[test]
+fn password_hash_traits() {
+    use commoncrypto::pbkdf2::Pbkdf2;
+    use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+
+    let parsed = PasswordHash::new(PHC_SHA256).unwrap();
+    assert!(Pbkdf2.verify_password(b"password", &parsed).is_ok());
+    assert!(Pbkdf2.verify_password(b"passw0rd", &parsed).is_err());
+
+    let salt = SaltString::encode_b64(b"saltsaltsaltsalt").unwrap();
+    let hash = Pbkdf2
+        .hash_password_customized(b"password", None, None, fast_params(), &salt)
+        .unwrap();
+    assert_eq!(hash.to_string(), PHC_SHA256);
+}
+
+#[cfg(not(target_vendor = "apple"))]
+mod injected_failures {
+    use commoncrypto::pbkdf2::{calibrate, pbkdf2, CCPseudoRandomAlgorithm};
+    use commoncrypto::Error;
+    use commoncrypto_shim::{clear_failures, inject_failure};
+    use commoncrypto_sys::CCCryptorStatus;
+    use std::os::raw::c_int;
+    use std::time::Duration;
+
+    #[test]
+    fn derive_failure() {
+        inject_failure(
+            "CCKeyDerivationPBKDF",
+            CCCryptorStatus::kCCMemoryFailure as c_int,
+        );
+        assert_eq!(
+            pbkdf2(
+                b"password",
+                b"salt",
+                CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256,
+                1,
+                32
+            )
+            .unwrap_err(),
+            Error::MemoryFailure
+        );
+        clear_failures();
+    }
+
+    #[test]
+    fn calibrate_failure() {
+        inject_failure("CCCalibratePBKDF", 0);
+        assert_eq!(
+            calibrate(
+                CCPseudoRandomAlgorithm::kCCPRFHmacAlgSHA256,
+                8,
+                16,
+                Duration::from_millis(10),
+                32
+            ),
+            Err(Error::ParamError)
+        );
+        clear_failures();
+    }
+}
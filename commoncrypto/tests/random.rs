@@ -0,0 +1,37 @@
+extern crate commoncrypto;
+#[cfg(feature = "rand_core")]
+extern crate rand_core;
+
+use commoncrypto::random::{fill, random_bytes};
+
+#[test]
+fn fill_buffer() {
+    let mut buf = [0u8; 64];
+    assert!(fill(&mut buf).is_ok());
+    assert!(buf.iter().any(|&b| b != 0));
+}
+
+#[test]
+fn generate_random_bytes() {
+    let first = random_bytes(32).expect("CCRandomGenerateBytes failed");
+    let second = random_bytes(32).expect("CCRandomGenerateBytes failed");
+    assert_eq!(first.len(), 32);
+    assert_ne!(first, second);
+    assert!(random_bytes(0).expect("empty").is_empty());
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn rng_core() {
+    use commoncrypto::random::CommonCryptoRng;
+    use rand_core::RngCore;
+
+    fn assert_crypto_rng<R: rand_core::CryptoRng + RngCore>(_: &R) {}
+
+    let mut rng = CommonCryptoRng;
+    assert_crypto_rng(&rng);
+    let mut buf = [0u8; 64];
+    rng.fill_bytes(&mut buf);
+    assert!(buf.iter().any(|&b| b != 0));
+    assert_ne!(rng.next_u64(), rng.next_u64());
+}
@@ -0,0 +1,52 @@
+extern crate commoncrypto;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+
+use commoncrypto::secret::SecretBytes;
+
+#[test]
+fn secret_debug_is_redacted() {
+    let secret = SecretBytes::from_slice(b"hunter2");
+    assert_eq!(format!("{:?}", secret), "SecretBytes { len: 7, .. }");
+}
+
+#[test]
+fn secret_from_vec() {
+    let mut bytes = Vec::with_capacity(16);
+    bytes.extend_from_slice(b"hunter2");
+    let secret = SecretBytes::from(bytes);
+    assert_eq!(secret.as_bytes(), b"hunter2");
+    assert_eq!(secret, SecretBytes::from_slice(b"hunter2"));
+    assert!(secret != SecretBytes::from_slice(b"hunter3"));
+}
+
+#[test]
+fn secret_as_key() {
+    let mut key = SecretBytes::new(4);
+    key.copy_from_slice(b"Jefe");
+    let mac = commoncrypto::hmac::hmac(
+        commoncrypto::hmac::CCHmacAlgorithm::kCCHmacAlgMD5,
+        &key,
+        b"what do ya want for nothing?",
+    );
+    assert_eq!(mac.len(), 16);
+}
+
+#[cfg(unix)]
+#[test]
+fn secret_lock() {
+    let mut secret = SecretBytes::from_slice(b"hunter2");
+    assert!(!secret.is_locked());
+    let locked = secret.lock().is_ok();
+    assert_eq!(secret.is_locked(), locked);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn secret_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut secret = SecretBytes::from_slice(b"hunter2");
+    secret.zeroize();
+    assert_eq!(secret.as_bytes(), &[0; 7]);
+}